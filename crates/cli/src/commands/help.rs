@@ -26,14 +26,47 @@ db-sniffer v{version} - A database introspector tool (by Borja Castellano)
  -u, --uri                | Str  | Define the connection string to the database                    | -u mysql://user:pass@ip:port/db
  -m, --mode               | Num  | Indicates the generation mode                                   | -m 1
  -o, --out                | Str  | Defines the output variable of the generation mode (optional)   | -o src/main/java/com/example/entities
+ --config                 | Str  | TOML generation recipe, db-sniffer.toml if present (optional)   | --config db-sniffer.toml
+ --package                | Str  | Java package instead of the one of the output dir (optional)    | --package com.example.model
+ --password-env           | Str  | Variable holding the password, DB_SNIFFER_PASSWORD (optional)   | --password-env DB_PASSWORD
+ --password-file          | Str  | File holding the password, instead of the uri one (optional)    | --password-file .db-password
+ --ask-password           |      | Asks for the password without echoing it (optional)             | --ask-password
+ --password-placeholder   | Str  | Written to hibernate.cfg.xml instead of the password (optional) | --password-placeholder '${{env.DB_PASSWORD}}'
+ --infer-fks              | Str  | Infers undeclared foreign keys from the column names (optional) | --infer-fks validate
+                          |      | 'validate' discards the relations with orphan rows              |
+ --version-columns        | Str  | Comma separated names of the optimistic lock columns (optional) | --version-columns version,row_version
+                          |      | Defaults to version,row_version. rowversion is always used      |
+ --audit                  | Str  | Maps the audit columns (created_at, updated_at...) (optional)   | --audit embedded
+                          |      | annotations, embedded or superclass                             |
+ --type-profile           | Str  | Java types of the temporal columns (optional)                   | --type-profile legacy
+                          |      | java-time (LocalDate, LocalDateTime...) or legacy (Date)        |
+ --primitives             |      | Primitive types for the NOT NULL columns (optional)             | --primitives
+ --keyless                | Str  | Mapping of the tables without a primary key (optional)          | --keyless unique
+                          |      | skip (default), unique, all-columns or id-class                 |
+ --inflections            | Str  | Comma separated singular:plural exceptions (optional)           | --inflections sms:sms
+                          |      | Same singular and plural for the uncountable words              |
+ --naming                 | Str  | Java names of the tables and columns (optional)                 | --naming strip-prefix
+                          |      | default, strip-prefix (tbl_, t_), hungarian (_str...), preserve |
+ --unidirectional         |      | Relations only in the tables with the foreign keys (optional)   | --unidirectional
+ --quote-mixed-case       |      | Quotes the mixed case table and column names in SQL (optional)  | --quote-mixed-case
+ --relations              | Str  | TOML file of the fetch, cascade and collections (optional)      | --relations relations.toml
+ --type-overrides         | Str  | TOML file overriding the Java/Hibernate types (optional)        | --type-overrides types.toml
+ --tables                 | Str  | Comma separated patterns of the tables to sniff (optional)      | --tables 'hr_*'
+                          |      | Globs or regular expressions between slashes (/^hr_\d+$/)       |
+ --exclude-tables         | Str  | Comma separated patterns of the tables to skip (optional)       | --exclude-tables '*_bak'
+ --columns                | Str  | Comma separated patterns of the columns to sniff (optional)     | --columns 'person.*'
+                          |      | table.column, a pattern without a table applies to all tables   |
+ --exclude-columns        | Str  | Comma separated patterns of the columns to skip (optional)      | --exclude-columns 'legacy_*'
+ --continue-on-error      |      | Skips the objects that can not be sniffed, warning (optional)   | --continue-on-error
+ --diagnostics            | Str  | JSON file to write the diagnostics to (optional)                | --diagnostics report.json
 
 - [ Generation modes ] -
 
-  Mode | Name
- ======+======
-     0 | DDL (Not implemented)
-     1 | Hibernate HBM.XML
-     2 | Hibernate with JPA Annotations (Not implemented)
+  Mode | Name          | Description
+ ======+===============+================================
+     0 | ddl           | DDL
+     1 | hibernate-xml | Hibernate HBM.XML
+     2 | hibernate-jpa | Hibernate with JPA Annotations
 
 Usage: sniffer [command] [options]... 
 "#
@@ -20,26 +20,28 @@ pub async fn try_execute(args: &str) -> Result<(), crate::Error> {
         None => return Err(crate::Error::NotEnoughArgsIntroduced),
     };
 
-    let command_instr = parts.map(String::from).collect::<Vec<String>>();
+    let command_instr = parts.collect::<Vec<String>>();
     let flags = map_flags(&command_instr);
 
     match main_command.as_str() {
-        "sniff" => Ok(Sniff::execute(flags).await),
-        "version" => Ok(Version::execute(flags).await),
-        _ => Ok(Help::execute(flags).await),
+        "sniff" => Sniff::execute(flags).await,
+        "version" => Version::execute(flags).await,
+        _ => Help::execute(flags).await,
     }
+
+    Ok(())
 }
 
-fn map_flags(args: &Vec<String>) -> HashMap<String, &str> {
+fn map_flags(args: &[String]) -> HashMap<String, &str> {
     let mut hash_map = HashMap::new();
     let mut i = 0;
 
     while i < args.len() {
         let actual_word = args[i].as_str();
-        let next_word = args[i + 1].as_str();
+        let next_word = args.get(i + 1).map(|s| s.as_str());
 
         if is_flag(actual_word) {
-            if i + 1 < args.len() && !is_flag(next_word) {
+            if let Some(next_word) = next_word.filter(|w| !is_flag(w)) {
                 hash_map.insert(actual_word.to_string(), next_word);
                 i += 2;
             } else {
@@ -106,7 +108,7 @@ mod tests {
     fn test_map_flags() {
         let mut args = Vec::new();
 
-        vec![
+        [
             "-n",
             "name",
             "a",
@@ -132,7 +134,7 @@ mod tests {
 
         let mut args = Vec::new();
 
-        vec!["mkt", "remove", "-n", "crates"]
+        ["mkt", "remove", "-n", "crates"]
             .iter()
             .for_each(|s| args.push(s.to_string()));
 
@@ -143,9 +145,9 @@ mod tests {
 
     #[test]
     fn test_is_flag() {
-        assert_eq!(is_flag("-n"), true);
-        assert_eq!(is_flag("--name"), true);
-        assert_eq!(is_flag("name"), false);
+        assert!(is_flag("-n"));
+        assert!(is_flag("--name"));
+        assert!(!is_flag("name"));
     }
     
     #[test]
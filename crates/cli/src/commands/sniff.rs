@@ -1,35 +1,45 @@
 use crate::commands::Command;
 use std::collections::HashMap;
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use db_sniffer::generators;
+use db_sniffer::{generators, Config, SniffFilter};
+use db_sniffer::generators::{
+    AuditMapping, GenerationMode, KeylessStrategy, RelationMapping, TypeMapping,
+};
+use db_sniffer::TypeProfile;
 
-pub enum SniffMode {
-    Ddl,
-    HibernateXML,
-    HibernateJPA,
-}
-
-impl FromStr for SniffMode {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "0" => Ok(SniffMode::Ddl),
-            "1" => Ok(SniffMode::HibernateXML),
-            "2" => Ok(SniffMode::HibernateJPA),
-            _ => Err(()),
-        }
-    }
-}
+/// Adds a table or column pattern to the filter
+type AddPattern = fn(&mut SniffFilter, &str) -> Result<(), db_sniffer::Error>;
 
 pub struct Sniff;
 
 impl Command for Sniff {
     async fn execute(flags: HashMap<String, &str>) {
-        let uri = match flags.get("-u").or_else(|| flags.get("--url")) {
-            Some(uri) => uri,
+        let config_path = match flags.get("--config") {
+            Some(&path) => Some(PathBuf::from(path)),
+            None => Some(PathBuf::from(Config::DEFAULT_FILE)).filter(|p| p.exists()),
+        };
+
+        // The flags override the settings of the configuration file
+        let mut config = match config_path {
+            Some(path) => match Config::load(&path) {
+                Ok(config) => config,
+                Err(e) => {
+                    println!("{e}");
+                    return;
+                }
+            },
+            None => Config::default(),
+        };
+
+        if let Some(&uri) = flags.get("-u").or_else(|| flags.get("--url")) {
+            config.set_url(Some(uri.to_string()));
+        }
+
+        let uri = match config.url() {
+            Some(uri) => uri.clone(),
             None => {
                 println!("Missing uri (-u)");
                 Self::show_usage();
@@ -37,31 +47,175 @@ impl Command for Sniff {
             }
         };
 
-        let results = match db_sniffer::sniff(uri).await {
-            Ok(a) => a,
-            Err(e) => {
-                println!("{e}",);
-                return;
+        if let Some(&infer_fks) = flags.get("--infer-fks") {
+            config.set_infer_relations(true);
+            config.set_validate_inferred_relations(infer_fks == "validate");
+        }
+
+        if let Some(&version_columns) = flags.get("--version-columns") {
+            config.set_version_columns(Some(
+                version_columns
+                    .split(',')
+                    .map(|c| c.trim().to_string())
+                    .filter(|c| !c.is_empty())
+                    .collect(),
+            ));
+        }
+
+        let filters: [(&str, AddPattern); 4] = [
+            ("--tables", SniffFilter::add_table_include),
+            ("--exclude-tables", SniffFilter::add_table_exclude),
+            ("--columns", SniffFilter::add_column_include),
+            ("--exclude-columns", SniffFilter::add_column_exclude),
+        ];
+
+        for (flag, add_pattern) in filters {
+            let Some(&patterns) = flags.get(flag) else {
+                continue;
+            };
+
+            for pattern in patterns.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                if let Err(e) = add_pattern(config.filter_mut(), pattern) {
+                    println!("{e}");
+                    return;
+                }
             }
-        };
+        }
 
-        let output = match flags.get("-o").or_else(|| flags.get("--out")) {
-            Some(&output) => {
-                if "." == output {
-                    env::current_dir().expect("Failed to get current directory")
-                } else {
-                    PathBuf::from(output)
+        if let Some(&audit) = flags.get("--audit") {
+            match AuditMapping::from_str(audit) {
+                Ok(mapping) => {
+                    config.set_audit(mapping);
+                }
+                Err(_) => {
+                    println!("Invalid audit mapping: {audit}");
+                    Self::show_usage();
+                    return;
                 }
             }
-            None => env::current_dir().expect("Failed to get current directory"),
-        };
+        }
+
+        if let Some(&type_overrides) = flags.get("--type-overrides") {
+            match TypeMapping::load(Path::new(type_overrides)) {
+                Ok(type_mapping) => {
+                    config.type_mapping_mut().merge(type_mapping);
+                }
+                Err(e) => {
+                    println!("{e}");
+                    return;
+                }
+            };
+        }
+
+        if let Some(&type_profile) = flags.get("--type-profile") {
+            match TypeProfile::from_str(type_profile) {
+                Ok(profile) => {
+                    config.type_mapping_mut().set_profile(profile);
+                }
+                Err(_) => {
+                    println!("Invalid type profile: {type_profile}");
+                    Self::show_usage();
+                    return;
+                }
+            }
+        }
+
+        if flags.contains_key("--primitives") {
+            config.set_primitives(true);
+        }
+
+        if let Some(&inflections) = flags.get("--inflections") {
+            for exception in inflections.split(',').filter(|e| !e.trim().is_empty()) {
+                match exception.split_once(':') {
+                    Some((singular, plural)) => {
+                        config
+                            .inflection_mut()
+                            .add_exception(singular.trim(), plural.trim());
+                    }
+                    None => {
+                        println!("Invalid inflection: {exception}, expected singular:plural");
+                        Self::show_usage();
+                        return;
+                    }
+                }
+            }
+        }
+
+        if let Some(&naming) = flags.get("--naming") {
+            config.set_naming(naming.to_string());
+        }
+
+        if let Some(&keyless) = flags.get("--keyless") {
+            match KeylessStrategy::from_str(keyless) {
+                Ok(strategy) => {
+                    config.set_keyless(strategy);
+                }
+                Err(_) => {
+                    println!("Invalid keyless strategy: {keyless}");
+                    Self::show_usage();
+                    return;
+                }
+            }
+        }
+
+        if let Some(&relations) = flags.get("--relations") {
+            match RelationMapping::load(Path::new(relations)) {
+                Ok(relation_mapping) => {
+                    config.set_relations(relation_mapping);
+                }
+                Err(e) => {
+                    println!("{e}");
+                    return;
+                }
+            }
+        }
+
+        if flags.contains_key("--unidirectional") {
+            config.set_bidirectional(false);
+        }
+
+        if flags.contains_key("--quote-mixed-case") {
+            config.set_quote_mixed_case(true);
+        }
+
+        if let Some(&package) = flags.get("--package") {
+            config.set_package(Some(package.to_string()));
+        }
+
+        if let Some(&password_env) = flags.get("--password-env") {
+            config.set_password_env(Some(password_env.to_string()));
+        }
 
-        let mode = flags
-            .get("-m")
-            .or_else(|| flags.get("--mode"))
-            .and_then(|m| SniffMode::from_str(m).ok());
+        if let Some(&password_file) = flags.get("--password-file") {
+            config.set_password_file(Some(PathBuf::from(password_file)));
+        }
 
-        let mode = if let Some(r) = mode {
+        if let Some(&placeholder) = flags.get("--password-placeholder") {
+            config.set_password_placeholder(Some(placeholder.to_string()));
+        }
+
+        if flags.contains_key("--continue-on-error") {
+            config.set_continue_on_error(true);
+        }
+
+        if let Some(&output) = flags.get("-o").or_else(|| flags.get("--out")) {
+            config.set_output(Some(PathBuf::from(output)));
+        }
+
+        if let Some(&mode) = flags.get("-m").or_else(|| flags.get("--mode")) {
+            match GenerationMode::from_str(mode) {
+                Ok(mode) => {
+                    config.set_mode(Some(mode));
+                }
+                Err(_) => {
+                    println!("Invalid mode: {mode}");
+                    Self::show_usage();
+                    return;
+                }
+            }
+        }
+
+        let mode = if let Some(r) = *config.mode() {
             r
         } else {
             println!("Missing mode (-m)");
@@ -69,16 +223,91 @@ impl Command for Sniff {
             return;
         };
 
-        let generator = match mode {
-            SniffMode::HibernateXML => generators::XMLGenerator::new(&results, &output).unwrap(),
-            SniffMode::Ddl => panic!("Not implemented mode"),
-            SniffMode::HibernateJPA => panic!("Not implemented mode"),
+        let generation_options = match config.generation_options() {
+            Ok(options) => options,
+            Err(e) => {
+                println!("{e}");
+                Self::show_usage();
+                return;
+            }
+        };
+
+        let mut options = match config.sniff_options() {
+            Ok(options) => options,
+            Err(e) => {
+                println!("{e}");
+                return;
+            }
         };
 
-        generator.generate();
+        if flags.contains_key("--ask-password") {
+            match rpassword::prompt_password("Password: ") {
+                Ok(password) => {
+                    options.set_password(Some(password));
+                }
+                Err(e) => {
+                    println!("Error reading the password: {e}");
+                    return;
+                }
+            }
+        }
+
+        let mut results = match db_sniffer::sniff_with_options(&uri, &options).await {
+            Ok(a) => a,
+            Err(e) => {
+                println!("{e}",);
+                return;
+            }
+        };
+
+        let output = match config.output() {
+            Some(output) if output != Path::new(".") => output.clone(),
+            _ => env::current_dir().expect("Failed to get current directory"),
+        };
+
+        if mode != GenerationMode::Ddl {
+            let mut diagnostics = results.diagnostics().clone();
+            diagnostics.extend(generators::diagnose(&results, &generation_options));
+            results.set_diagnostics(diagnostics);
+        }
+
+        let generated = match mode {
+            GenerationMode::HibernateXml => {
+                generators::XMLGenerator::new_with_options(&results, &output, generation_options)
+                    .map(|g| g.generate())
+            }
+            GenerationMode::Ddl => {
+                generators::DDLGenerator::new_with_options(&results, &output, generation_options)
+                    .generate();
+                Some(())
+            }
+            GenerationMode::HibernateJpa => {
+                generators::JPAGenerator::new_with_options(&results, &output, generation_options)
+                    .map(|g| g.generate())
+            }
+        };
+
+        // The generators already explain why they could not be created
+        if generated.is_none() {
+            return;
+        }
+
+        if !results.diagnostics().is_empty() {
+            println!("\nDiagnostics:");
+
+            for diagnostic in results.diagnostics() {
+                println!(" - {diagnostic}");
+            }
+        }
+
+        if let Some(&path) = flags.get("--diagnostics")
+            && let Err(e) = fs::write(path, results.diagnostics_json())
+        {
+            println!("Error writing the diagnostics to {path}: {e}");
+        }
     }
     
     fn show_usage() {
-        println!("USAGE: {} sniff -u <uri> -m <mode> [-o <output>]", env::args().next().unwrap_or("sniffer".to_string()));
+        println!("USAGE: {} sniff -u <uri> -m <mode> [-o <output>] [--config <file>] [--package <name>] [--password-env <variable>] [--password-file <file>] [--ask-password] [--password-placeholder <text>] [--infer-fks [validate]] [--version-columns <names>] [--audit <mapping>] [--type-profile <profile>] [--type-overrides <file>] [--primitives] [--keyless <strategy>] [--inflections <pairs>] [--naming <strategy>] [--unidirectional] [--quote-mixed-case] [--relations <file>] [--tables <patterns>] [--exclude-tables <patterns>] [--columns <patterns>] [--exclude-columns <patterns>] [--continue-on-error] [--diagnostics <file>]", env::args().next().unwrap_or("sniffer".to_string()));
     }
 }
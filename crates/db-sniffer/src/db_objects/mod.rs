@@ -1,7 +1,8 @@
-use getset::Getters;
+use getset::{Getters, MutGetters, Setters};
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum ColumnType {
     Integer(i32),
     Text(i32),
@@ -12,10 +13,17 @@ pub enum ColumnType {
     Date,
     Time,
     DateTime,
+    /// Point in time stored in UTC, e.g. MySQL `timestamp`
+    Timestamp,
+    /// Date and time with its offset from UTC, e.g. SQL Server `datetimeoffset`
+    DateTimeOffset,
     Boolean,
     Blob(i32),
     Decimal(i32, i32),
     Numeric(i32),
+    Uuid,
+    /// SQL Server `rowversion` (also reported as `timestamp`), changed by the database on every write
+    RowVersion,
 }
 
 impl FromStr for ColumnType {
@@ -23,7 +31,7 @@ impl FromStr for ColumnType {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s.replace(" ", "");
-        let regex = regex::Regex::new(r"(?P<type_name>[a-z]+)(\((?P<values>[\d,]+)\))?$")
+        let regex = regex::Regex::new(r"(?P<type_name>[a-z][a-z0-9]*)(\((?P<values>[\d,]+)\))?$")
             .expect("invalid regex");
 
         let Some((type_name, values)) = regex.captures(&s).map(|captures| {
@@ -61,7 +69,9 @@ impl FromStr for ColumnType {
             "double" => Ok(ColumnType::Double(0)),
             "date" => Ok(ColumnType::Date),
             "time" => Ok(ColumnType::Time),
-            "datetime" | "timestamp" => Ok(ColumnType::DateTime),
+            "datetime" | "datetime2" | "smalldatetime" => Ok(ColumnType::DateTime),
+            "timestamp" => Ok(ColumnType::Timestamp),
+            "datetimeoffset" => Ok(ColumnType::DateTimeOffset),
             "boolean" | "bool" => Ok(ColumnType::Boolean),
             "blob" => Ok(ColumnType::Blob(0)),
             "decimal" => Ok(ColumnType::Decimal(
@@ -69,36 +79,70 @@ impl FromStr for ColumnType {
                 second_value,
             )),
             "numeric" => Ok(ColumnType::Numeric(0)),
+            "uniqueidentifier" | "uuid" => Ok(ColumnType::Uuid),
+            "rowversion" => Ok(ColumnType::RowVersion),
             _ => Err(()),
         }
     }
 }
 
 impl ColumnType {
-    pub fn to_hibernate(&self) -> String {
+    pub fn to_hibernate(&self, profile: TypeProfile) -> String {
+        if profile == TypeProfile::JavaTime {
+            match self {
+                ColumnType::Date => return "LocalDate".to_string(),
+                ColumnType::Time => return "LocalTime".to_string(),
+                ColumnType::DateTime => return "LocalDateTime".to_string(),
+                ColumnType::Timestamp => return "Instant".to_string(),
+                ColumnType::DateTimeOffset => return "OffsetDateTime".to_string(),
+                _ => {}
+            }
+        }
+
         match self {
             ColumnType::Integer(_) => "int".to_string(),
             ColumnType::Text(_) | ColumnType::Varchar(_) => "string".to_string(),
             ColumnType::Blob(_) => "binary".to_string(),
             ColumnType::Boolean => "boolean".to_string(),
             ColumnType::Date => "date".to_string(),
-            ColumnType::DateTime => "timestamp".to_string(),
+            ColumnType::DateTime | ColumnType::Timestamp | ColumnType::DateTimeOffset => {
+                "timestamp".to_string()
+            }
             ColumnType::Time => "time".to_string(),
             ColumnType::Double(_) => "double".to_string(),
             ColumnType::Float(_) => "float".to_string(),
             ColumnType::Char(_) => "char".to_string(),
             ColumnType::Numeric(_) => "big_decimal".to_string(),
             ColumnType::Decimal(precision, scale) => "big_decimal".to_string(),
+            ColumnType::Uuid => "uuid".to_string(),
+            ColumnType::RowVersion => "binary".to_string(),
         }
     }
 
-    pub fn to_java(&self) -> dotjava::Type {
+    pub fn to_java(&self, profile: TypeProfile) -> dotjava::Type {
+        if profile == TypeProfile::JavaTime {
+            let java_time = |name: &str| dotjava::Type::new(name.to_string(), "java.time".to_string());
+
+            match self {
+                ColumnType::Date => return java_time("LocalDate"),
+                ColumnType::Time => return java_time("LocalTime"),
+                ColumnType::DateTime => return java_time("LocalDateTime"),
+                ColumnType::Timestamp => return java_time("Instant"),
+                ColumnType::DateTimeOffset => return java_time("OffsetDateTime"),
+                _ => {}
+            }
+        }
+
         match self {
             ColumnType::Integer(_) => dotjava::Type::integer(),
             ColumnType::Text(_) | ColumnType::Varchar(_) => dotjava::Type::string(),
             ColumnType::Blob(_) => dotjava::Type::new("byte[]".to_string(), "".to_string()),
             ColumnType::Boolean => dotjava::Type::boolean(),
-            ColumnType::Date | ColumnType::DateTime | ColumnType::Time => {
+            ColumnType::Date
+            | ColumnType::DateTime
+            | ColumnType::Time
+            | ColumnType::Timestamp
+            | ColumnType::DateTimeOffset => {
                 dotjava::Type::new("Date".to_string(), "java.util".to_string())
             }
             ColumnType::Double(_) => dotjava::Type::double(),
@@ -110,17 +154,50 @@ impl ColumnType {
             ColumnType::Decimal(precision, scale) => {
                 dotjava::Type::new("BigDecimal".to_string(), "java.math".to_string())
             }
+            ColumnType::Uuid => dotjava::Type::new("UUID".to_string(), "java.util".to_string()),
+            ColumnType::RowVersion => dotjava::Type::new("byte[]".to_string(), "".to_string()),
         }
     }
 }
 
-#[derive(PartialEq, Debug)]
+/// Java types used for the temporal columns
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum TypeProfile {
+    /// `java.time` types, handled natively since Hibernate 6
+    #[default]
+    JavaTime,
+    /// `java.util.Date` with the `date`/`time`/`timestamp` Hibernate types
+    Legacy,
+}
+
+impl FromStr for TypeProfile {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "java-time" | "javatime" => Ok(TypeProfile::JavaTime),
+            "legacy" => Ok(TypeProfile::Legacy),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
 pub enum GenerationType {
     None,
+    /// Identity column with the default seed and increment (1, 1)
     AutoIncrement,
+    /// Sequence (name, start, increment) used as the default value of the column
+    Sequence(String, i64, i64),
+    /// Identity column (seed, increment)
+    Identity(i64, i64),
+    /// UUID generated by the database, e.g. `uniqueidentifier DEFAULT NEWID()`
+    Uuid,
+    /// Any other default expression of the column
+    Default(String),
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum KeyType {
     Primary(GenerationType),
     Unique,
@@ -129,14 +206,19 @@ pub enum KeyType {
 
 pub struct TableId(Vec<Column>);
 
-#[derive(Getters, PartialEq, Debug)]
+#[derive(Getters, MutGetters, Setters, Clone, PartialEq, Debug)]
 pub struct Table {
     #[get = "pub"]
     name: String,
-    #[get = "pub"]
+    #[getset(get = "pub", get_mut = "pub")]
     columns: Vec<Column>,
-    #[get = "pub"]
+    #[getset(get = "pub", get_mut = "pub")]
     references: Vec<Relation>,
+    /// Description of the table documented in the database
+    #[getset(get = "pub", set = "pub")]
+    comment: Option<String>,
+    #[get = "pub"]
+    triggers: Vec<Trigger>,
 }
 
 impl Table {
@@ -145,6 +227,8 @@ impl Table {
             name: name.to_string(),
             columns: Vec::new(),
             references: Vec::new(),
+            comment: None,
+            triggers: Vec::new(),
         }
     }
 
@@ -172,9 +256,168 @@ impl Table {
     pub fn column(&self, name: &str) -> Option<&Column> {
         self.columns.iter().find(|c| c.id.name == name)
     }
+
+    /// Column used for the optimistic locking of the rows
+    pub fn version_column(&self) -> Option<&Column> {
+        self.columns.iter().find(|c| c.version)
+    }
+
+    pub fn add_trigger(&mut self, trigger: Trigger) {
+        self.triggers.push(trigger);
+    }
+
+    /// Triggers that seem to assign a value to the column
+    pub fn triggers_modifying(&self, column: &str) -> Vec<&Trigger> {
+        self.triggers
+            .iter()
+            .filter(|t| {
+                t.modified_columns()
+                    .iter()
+                    .any(|c| c.eq_ignore_ascii_case(column))
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TriggerTiming {
+    Before,
+    After,
+    InsteadOf,
+}
+
+impl FromStr for TriggerTiming {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().replace(" ", "").as_str() {
+            "BEFORE" => Ok(TriggerTiming::Before),
+            "AFTER" => Ok(TriggerTiming::After),
+            "INSTEADOF" => Ok(TriggerTiming::InsteadOf),
+            _ => Err(()),
+        }
+    }
 }
 
-#[derive(PartialEq, Debug)]
+impl Display for TriggerTiming {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TriggerTiming::Before => write!(f, "BEFORE"),
+            TriggerTiming::After => write!(f, "AFTER"),
+            TriggerTiming::InsteadOf => write!(f, "INSTEAD OF"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TriggerEvent {
+    Insert,
+    Update,
+    Delete,
+}
+
+impl FromStr for TriggerEvent {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "INSERT" => Ok(TriggerEvent::Insert),
+            "UPDATE" => Ok(TriggerEvent::Update),
+            "DELETE" => Ok(TriggerEvent::Delete),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Display for TriggerEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TriggerEvent::Insert => write!(f, "INSERT"),
+            TriggerEvent::Update => write!(f, "UPDATE"),
+            TriggerEvent::Delete => write!(f, "DELETE"),
+        }
+    }
+}
+
+#[derive(Getters, Clone, PartialEq, Debug)]
+pub struct Trigger {
+    #[get = "pub"]
+    name: String,
+    #[get = "pub"]
+    table: String,
+    #[get = "pub"]
+    timing: TriggerTiming,
+    #[get = "pub"]
+    events: Vec<TriggerEvent>,
+    /// Statement run by the trigger. SQL Server reports the whole `CREATE TRIGGER` definition.
+    #[get = "pub"]
+    body: String,
+}
+
+impl Trigger {
+    pub fn new(
+        name: &str,
+        table: &str,
+        timing: TriggerTiming,
+        events: Vec<TriggerEvent>,
+        body: &str,
+    ) -> Self {
+        Trigger {
+            name: name.to_string(),
+            table: table.to_string(),
+            timing,
+            events,
+            body: body.to_string(),
+        }
+    }
+
+    /// Description of when the trigger fires, e.g. `BEFORE INSERT OR UPDATE`
+    pub fn fires_on(&self) -> String {
+        let events = self
+            .events
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<String>>()
+            .join(" OR ");
+
+        format!("{} {events}", self.timing)
+    }
+
+    /// Columns assigned in the `SET` clauses of the body, e.g. `SET NEW.updated_at = NOW()` or
+    /// `UPDATE t SET t.updated_at = GETDATE() FROM ...`. It is a heuristic, the body is not parsed.
+    pub fn modified_columns(&self) -> Vec<String> {
+        let set_regex = regex::Regex::new(r"(?is)\bSET\b(?P<assignments>.*?)(\bWHERE\b|\bFROM\b|\bEND\b|;|$)")
+            .expect("invalid regex");
+
+        let mut columns = Vec::new();
+
+        for captures in set_regex.captures_iter(&self.body) {
+            for assignment in captures["assignments"].split(',') {
+                let Some((target, _)) = assignment.split_once('=') else {
+                    continue;
+                };
+
+                let column = target
+                    .trim()
+                    .rsplit('.')
+                    .next()
+                    .unwrap_or_default()
+                    .trim_matches(|c| matches!(c, '[' | ']' | '`' | '"'));
+
+                if !column.is_empty()
+                    && column.chars().all(|c| c.is_alphanumeric() || c == '_')
+                    && !columns.iter().any(|c: &String| c.eq_ignore_ascii_case(column))
+                {
+                    columns.push(column.to_string());
+                }
+            }
+        }
+
+        columns
+    }
+}
+
+#[derive(PartialEq, Clone, Debug)]
 pub enum RelationType {
     OneToOne,
     OneToMany,
@@ -193,7 +436,7 @@ impl RelationType {
     }
 }
 
-#[derive(Getters, PartialEq, Debug)]
+#[derive(Getters, Setters, Clone, Debug)]
 pub struct Relation {
     #[get = "pub"]
     from: Vec<ColumnId>,
@@ -201,15 +444,45 @@ pub struct Relation {
     to: Vec<ColumnId>,
     #[get = "pub"]
     r#type: RelationType,
+    /// The relation was deduced from the column names instead of being declared as a foreign key
+    #[getset(get = "pub", set = "pub")]
+    inferred: bool,
+    /// Name of the foreign key constraint declaring the relation
+    #[getset(get = "pub", set = "pub")]
+    name: Option<String>,
 }
 
 impl Relation {
-    pub fn new(from: Vec<ColumnId>, to: Vec<ColumnId>, r#type: RelationType) -> Self {
-        if from.len() != to.len() {
-            panic!("Invalid relation. |From columns| != |To columns|")
+    pub fn new(
+        from: Vec<ColumnId>,
+        to: Vec<ColumnId>,
+        r#type: RelationType,
+    ) -> Result<Self, crate::Error> {
+        if from.is_empty() || from.len() != to.len() {
+            return Err(crate::Error::InvalidRelationError(format!(
+                "{} columns referencing {} columns",
+                from.len(),
+                to.len()
+            )));
         }
 
-        Relation { from, to, r#type }
+        Ok(Relation {
+            from,
+            to,
+            r#type,
+            inferred: false,
+            name: None,
+        })
+    }
+}
+
+/// The constraint name is left out: each database names the unnamed foreign keys differently
+impl PartialEq for Relation {
+    fn eq(&self, other: &Self) -> bool {
+        self.from == other.from
+            && self.to == other.to
+            && self.r#type == other.r#type
+            && self.inferred == other.inferred
     }
 }
 
@@ -230,15 +503,24 @@ impl ColumnId {
     }
 }
 
-#[derive(Getters, PartialEq, Debug)]
+#[derive(Getters, Setters, Clone, PartialEq, Debug)]
 pub struct Column {
     id: ColumnId,
     #[get = "pub"]
     r#type: ColumnType,
     #[get = "pub"]
     nullable: bool,
-    #[get = "pub"]
+    #[getset(get = "pub", set = "pub")]
     key: KeyType,
+    /// Description of the column documented in the database
+    #[getset(get = "pub", set = "pub")]
+    comment: Option<String>,
+    /// Expression computing the value of the column, the database does not accept writes to it
+    #[getset(get = "pub", set = "pub")]
+    computed: Option<Computed>,
+    /// The column is the optimistic lock of its table
+    #[getset(get = "pub", set = "pub")]
+    version: bool,
 }
 
 impl Column {
@@ -248,6 +530,9 @@ impl Column {
             r#type,
             nullable,
             key,
+            comment: None,
+            computed: None,
+            version: false,
         }
     }
 
@@ -255,6 +540,16 @@ impl Column {
         !self.nullable
     }
 
+    pub fn is_computed(&self) -> bool {
+        self.computed.is_some()
+    }
+
+    /// The database writes the value, computed or a `rowversion`, so it is never inserted nor
+    /// updated
+    pub fn is_database_generated(&self) -> bool {
+        self.is_computed() || matches!(self.r#type, ColumnType::RowVersion)
+    }
+
     pub fn name(&self) -> &str {
         &self.id.name
     }
@@ -264,12 +559,32 @@ impl Column {
     }
 }
 
-#[derive(Getters, PartialEq, Debug)]
+#[derive(Getters, Clone, PartialEq, Debug)]
+pub struct Computed {
+    #[get = "pub"]
+    expression: String,
+    /// The value is saved in the table instead of being computed when read
+    #[get = "pub"]
+    stored: bool,
+}
+
+impl Computed {
+    pub fn new(expression: &str, stored: bool) -> Self {
+        Computed {
+            expression: expression.to_string(),
+            stored,
+        }
+    }
+}
+
+#[derive(Getters, MutGetters, Clone, PartialEq, Debug)]
 pub struct Database {
     #[get = "pub"]
     name: String,
-    #[get = "pub"]
+    #[getset(get = "pub", get_mut = "pub")]
     tables: Vec<Table>,
+    #[get = "pub"]
+    routines: Vec<Routine>,
 }
 
 impl Database {
@@ -277,6 +592,7 @@ impl Database {
         Database {
             name: db_name.to_string(),
             tables: Vec::new(),
+            routines: Vec::new(),
         }
     }
     
@@ -284,10 +600,18 @@ impl Database {
         self.tables.push(table);
     }
 
+    pub fn add_routine(&mut self, routine: Routine) {
+        self.routines.push(routine);
+    }
+
     pub fn table(&self, name: &str) -> Option<&Table> {
         self.tables.iter().find(|t| t.name == name)
     }
 
+    pub fn table_mut(&mut self, name: &str) -> Option<&mut Table> {
+        self.tables.iter_mut().find(|t| t.name == name)
+    }
+
     pub fn column(&self, column_id: &ColumnId) -> Option<&Column> {
         self.tables.iter().find_map(|t| t.column(&column_id.name))
     }
@@ -298,9 +622,7 @@ impl Database {
             .flat_map(|t| {
                 t.references
                     .iter()
-                    .filter(|r| {
-                        r.to().first().expect("Relation can not be empty").table == table_name
-                    })
+                    .filter(|r| r.to().first().is_some_and(|c| c.table == table_name))
                     .collect::<Vec<&Relation>>()
             })
             .collect()
@@ -310,10 +632,87 @@ impl Database {
         self.tables
             .iter()
             .find(|t| t.name == table_name)
-            .expect("Table not found. Should not happen")
-            .references()
-            .iter()
-            .collect()
+            .map(|t| t.references().iter().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum RoutineType {
+    Procedure,
+    Function,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ParameterMode {
+    In,
+    Out,
+    InOut,
+}
+
+impl FromStr for ParameterMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "IN" => Ok(ParameterMode::In),
+            "OUT" => Ok(ParameterMode::Out),
+            "INOUT" => Ok(ParameterMode::InOut),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Getters, Clone, PartialEq, Debug)]
+pub struct RoutineParameter {
+    #[get = "pub"]
+    name: String,
+    #[get = "pub"]
+    r#type: ColumnType,
+    #[get = "pub"]
+    mode: ParameterMode,
+}
+
+impl RoutineParameter {
+    pub fn new(name: &str, r#type: ColumnType, mode: ParameterMode) -> Self {
+        RoutineParameter {
+            name: name.to_string(),
+            r#type,
+            mode,
+        }
+    }
+}
+
+/// Stored procedure or function of the database
+#[derive(Getters, Setters, Clone, PartialEq, Debug)]
+pub struct Routine {
+    #[get = "pub"]
+    name: String,
+    #[get = "pub"]
+    r#type: RoutineType,
+    #[get = "pub"]
+    parameters: Vec<RoutineParameter>,
+    /// Type returned by a scalar function
+    #[getset(get = "pub", set = "pub")]
+    return_type: Option<ColumnType>,
+    /// (name, type) of the columns of the first result set, when the database can describe it
+    #[getset(get = "pub", set = "pub")]
+    result_columns: Vec<(String, ColumnType)>,
+}
+
+impl Routine {
+    pub fn new(name: &str, r#type: RoutineType) -> Self {
+        Routine {
+            name: name.to_string(),
+            r#type,
+            parameters: Vec::new(),
+            return_type: None,
+            result_columns: Vec::new(),
+        }
+    }
+
+    pub fn add_parameter(&mut self, parameter: RoutineParameter) {
+        self.parameters.push(parameter);
     }
 }
 
@@ -338,6 +737,27 @@ pub enum Dbms {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_column_type_type_profile() {
+        let java_time = TypeProfile::JavaTime;
+        let legacy = TypeProfile::Legacy;
+
+        let java_name = |t: ColumnType, p: TypeProfile| String::from(t.to_java(p));
+
+        assert_eq!(java_name(ColumnType::Date, java_time), "LocalDate");
+        assert_eq!(java_name(ColumnType::DateTime, java_time), "LocalDateTime");
+        assert_eq!(java_name(ColumnType::Timestamp, java_time), "Instant");
+        assert_eq!(
+            java_name(ColumnType::DateTimeOffset, java_time),
+            "OffsetDateTime"
+        );
+        assert_eq!(java_name(ColumnType::Time, legacy), "Date");
+
+        assert_eq!(ColumnType::Time.to_hibernate(java_time), "LocalTime");
+        assert_eq!(ColumnType::Timestamp.to_hibernate(legacy), "timestamp");
+        assert_eq!(ColumnType::Integer(0).to_hibernate(java_time), "int");
+    }
+
     #[test]
     fn test_column_type_from_str() {
         assert_eq!("int".parse::<ColumnType>(), Ok(ColumnType::Integer(0)));
@@ -350,7 +770,9 @@ mod tests {
         assert_eq!("date".parse::<ColumnType>(), Ok(ColumnType::Date));
         assert_eq!("time".parse::<ColumnType>(), Ok(ColumnType::Time));
         assert_eq!("datetime".parse::<ColumnType>(), Ok(ColumnType::DateTime));
-        assert_eq!("timestamp".parse::<ColumnType>(), Ok(ColumnType::DateTime));
+        assert_eq!("timestamp".parse::<ColumnType>(), Ok(ColumnType::Timestamp));
+        assert_eq!("datetime2".parse::<ColumnType>(), Ok(ColumnType::DateTime));
+        assert_eq!("datetimeoffset".parse::<ColumnType>(), Ok(ColumnType::DateTimeOffset));
         assert_eq!("boolean".parse::<ColumnType>(), Ok(ColumnType::Boolean));
         assert_eq!("bool".parse::<ColumnType>(), Ok(ColumnType::Boolean));
         assert_eq!("blob".parse::<ColumnType>(), Ok(ColumnType::Blob(0)));
@@ -359,6 +781,8 @@ mod tests {
             Ok(ColumnType::Decimal(0, 0))
         );
         assert_eq!("numeric".parse::<ColumnType>(), Ok(ColumnType::Numeric(0)));
+        assert_eq!("uniqueidentifier".parse::<ColumnType>(), Ok(ColumnType::Uuid));
+        assert_eq!("rowversion".parse::<ColumnType>(), Ok(ColumnType::RowVersion));
         assert_eq!("invalid".parse::<ColumnType>(), Err(()));
 
         assert_eq!("char(3)".parse::<ColumnType>(), Ok(ColumnType::Char(3)));
@@ -373,4 +797,43 @@ mod tests {
             Ok(ColumnType::Decimal(10, 2))
         );
     }
+
+    #[test]
+    fn test_relation_new() {
+        let from = vec![ColumnId::new("phone", "person_id")];
+        let to = vec![ColumnId::new("person", "id")];
+
+        assert!(Relation::new(from.clone(), to.clone(), RelationType::ManyToOne).is_ok());
+        assert!(Relation::new(from, vec![], RelationType::ManyToOne).is_err());
+        assert!(Relation::new(vec![], vec![], RelationType::ManyToOne).is_err());
+    }
+
+    #[test]
+    fn test_trigger_modified_columns() {
+        let mysql_trigger = Trigger::new(
+            "person_bu",
+            "person",
+            TriggerTiming::Before,
+            vec![TriggerEvent::Update],
+            "BEGIN SET NEW.updated_at = NOW(), NEW.`version` = OLD.version + 1; END",
+        );
+
+        assert_eq!(mysql_trigger.modified_columns(), vec!["updated_at", "version"]);
+
+        let mssql_trigger = Trigger::new(
+            "person_au",
+            "person",
+            TriggerTiming::After,
+            vec![TriggerEvent::Insert, TriggerEvent::Update],
+            "CREATE TRIGGER person_au ON person AFTER INSERT, UPDATE AS
+            BEGIN
+                UPDATE p SET p.[updated_at] = GETDATE(), full_name = CONCAT(p.name, ' ', p.surname)
+                FROM person p JOIN inserted i ON p.id = i.id
+                WHERE p.id > 0;
+            END",
+        );
+
+        assert_eq!(mssql_trigger.modified_columns(), vec!["updated_at", "full_name"]);
+        assert_eq!(mssql_trigger.fires_on(), "AFTER INSERT OR UPDATE");
+    }
 }
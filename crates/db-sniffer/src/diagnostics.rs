@@ -0,0 +1,305 @@
+use crate::db_objects::Database;
+use crate::naming::NamingStrategy;
+use getset::Getters;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Warning,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// The table has no primary key, so its entity has no id
+    MissingPrimaryKey,
+    /// The column was skipped as its type can not be mapped
+    UnsupportedType,
+    /// The relation references a table that was not sniffed
+    UnknownRelationTable,
+    /// Several tables or columns are mapped to the same Java name
+    NameCollision,
+    /// The relation was deduced from the column names instead of being declared
+    InferredRelation,
+    /// The object could not be sniffed and was skipped
+    SkippedObject,
+    /// The converter of the type override of the column could not be generated
+    UnsupportedConversion,
+}
+
+impl DiagnosticKind {
+    pub fn severity(&self) -> Severity {
+        match self {
+            DiagnosticKind::InferredRelation => Severity::Info,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+/// Issue found while sniffing that may need a review of the generated code
+#[derive(Getters, Serialize, Clone, Debug, PartialEq)]
+#[get = "pub"]
+pub struct Diagnostic {
+    kind: DiagnosticKind,
+    severity: Severity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    table: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<String>,
+    message: String,
+}
+
+impl Diagnostic {
+    pub fn new(
+        kind: DiagnosticKind,
+        table: Option<&str>,
+        column: Option<&str>,
+        message: &str,
+    ) -> Self {
+        Diagnostic {
+            kind,
+            severity: kind.severity(),
+            table: table.map(|t| t.to_string()),
+            column: column.map(|c| c.to_string()),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<crate::Error> for Diagnostic {
+    fn from(value: crate::Error) -> Self {
+        let message = value.to_string();
+
+        match value {
+            crate::Error::UnsupportedTypeError { table, column, .. } => Diagnostic::new(
+                DiagnosticKind::UnsupportedType,
+                Some(&table),
+                Some(&column),
+                &message,
+            ),
+            crate::Error::TableError { table, .. } => {
+                Diagnostic::new(DiagnosticKind::SkippedObject, Some(&table), None, &message)
+            }
+            _ => Diagnostic::new(DiagnosticKind::SkippedObject, None, None, &message),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+        };
+
+        write!(f, "[{severity}] {}", self.message)
+    }
+}
+
+/// Diagnostics of the sniffed database: tables without a primary key, relations to tables that
+/// were not sniffed and inferred relations
+pub(crate) fn diagnose(database: &Database) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for table in database.tables() {
+        if table.ids().is_empty() {
+            diagnostics.push(Diagnostic::new(
+                DiagnosticKind::MissingPrimaryKey,
+                Some(table.name()),
+                None,
+                &format!("Table {} has no primary key", table.name()),
+            ));
+        }
+
+        for relation in table.references() {
+            let (Some(from), Some(to)) = (relation.from().first(), relation.to().first()) else {
+                continue;
+            };
+
+            let names = format!("{}.{} -> {}.{}", from.table(), from.name(), to.table(), to.name());
+
+            if database.table(to.table()).is_none() {
+                diagnostics.push(Diagnostic::new(
+                    DiagnosticKind::UnknownRelationTable,
+                    Some(table.name()),
+                    Some(from.name()),
+                    &format!("Relation {names} references a table that was not sniffed"),
+                ));
+            }
+
+            if *relation.inferred() {
+                diagnostics.push(Diagnostic::new(
+                    DiagnosticKind::InferredRelation,
+                    Some(table.name()),
+                    Some(from.name()),
+                    &format!("Relation {names} was inferred from the column name"),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Diagnostics of the database names that collide once converted to Java with the naming
+pub(crate) fn name_collisions(database: &Database, naming: &dyn NamingStrategy) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for table in database.tables() {
+        let columns = table.columns().iter().map(|c| c.name());
+
+        for (field, names) in collisions(columns, |c| naming.field_name(c)) {
+            diagnostics.push(Diagnostic::new(
+                DiagnosticKind::NameCollision,
+                Some(table.name()),
+                None,
+                &format!(
+                    "Columns {} of the table {} are mapped to the field {field}",
+                    names.join(", "),
+                    table.name()
+                ),
+            ));
+        }
+    }
+
+    let tables = database.tables().iter().map(|t| t.name().as_str());
+
+    for (class, names) in collisions(tables, |t| naming.class_name(t)) {
+        diagnostics.push(Diagnostic::new(
+            DiagnosticKind::NameCollision,
+            None,
+            None,
+            &format!("Tables {} are mapped to the class {class}", names.join(", ")),
+        ));
+    }
+
+    diagnostics
+}
+
+/// Java names shared by several database names
+fn collisions<'a>(
+    names: impl Iterator<Item = &'a str>,
+    to_java: impl Fn(&str) -> String,
+) -> Vec<(String, Vec<&'a str>)> {
+    let mut java_names: BTreeMap<String, Vec<&'a str>> = BTreeMap::new();
+
+    for name in names {
+        java_names.entry(to_java(name)).or_default().push(name);
+    }
+
+    java_names
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_objects::{
+        Column, ColumnId, ColumnType, GenerationType, KeyType, Relation, RelationType, Table,
+    };
+    use crate::naming::{DefaultNaming, Inflection, PrefixStrippingNaming};
+
+    #[test]
+    fn test_diagnose() {
+        let column = |table: &str, name: &str, key: KeyType| {
+            Column::new(ColumnId::new(table, name), ColumnType::Integer(0), false, key)
+        };
+
+        let mut person = Table::new("person");
+        person.add_column(column("person", "id", KeyType::Primary(GenerationType::None)));
+        person.add_column(column("person", "first_name", KeyType::None));
+        person.add_column(column("person", "firstName", KeyType::None));
+        person.add_column(column("person", "department_id", KeyType::None));
+
+        let mut relation = Relation::new(
+            vec![ColumnId::new("person", "department_id")],
+            vec![ColumnId::new("department", "id")],
+            RelationType::ManyToOne,
+        )
+        .unwrap();
+        relation.set_inferred(true);
+        person.add_reference_to(relation);
+
+        let mut persons = Table::new("persons");
+        persons.add_column(column("persons", "name", KeyType::None));
+
+        let mut database = Database::new("test_db");
+        database.add_table(person);
+        database.add_table(persons);
+
+        let kinds = diagnose(&database)
+            .iter()
+            .map(|d| (*d.kind(), d.table().clone()))
+            .collect::<Vec<(DiagnosticKind, Option<String>)>>();
+
+        let person = Some("person".to_string());
+
+        assert_eq!(
+            kinds,
+            vec![
+                (DiagnosticKind::UnknownRelationTable, person.clone()),
+                (DiagnosticKind::InferredRelation, person),
+                (DiagnosticKind::MissingPrimaryKey, Some("persons".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_name_collisions() {
+        let column = |table: &str, name: &str| {
+            Column::new(ColumnId::new(table, name), ColumnType::Integer(0), false, KeyType::None)
+        };
+
+        let mut orders = Table::new("orders");
+        orders.add_column(column("orders", "first_name"));
+        orders.add_column(column("orders", "firstName"));
+
+        let mut database = Database::new("test_db");
+        database.add_table(orders);
+        database.add_table(Table::new("order"));
+        database.add_table(Table::new("tbl_order"));
+
+        let messages = |naming: &dyn NamingStrategy| {
+            name_collisions(&database, naming)
+                .iter()
+                .map(|d| d.message().clone())
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(
+            messages(&DefaultNaming::default()),
+            vec![
+                "Columns first_name, firstName of the table orders are mapped to the field firstName",
+                "Tables orders, order are mapped to the class Order",
+            ]
+        );
+        assert_eq!(
+            messages(&PrefixStrippingNaming::new(Inflection::default())),
+            vec![
+                "Columns first_name, firstName of the table orders are mapped to the field firstName",
+                "Tables orders, order, tbl_order are mapped to the class Order",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_json() {
+        let diagnostic: Diagnostic = crate::Error::UnsupportedTypeError {
+            table: "person".to_string(),
+            column: "location".to_string(),
+            r#type: "geometry".to_string(),
+        }
+        .into();
+
+        assert_eq!(
+            serde_json::to_string(&diagnostic).unwrap(),
+            r#"{"kind":"unsupported_type","severity":"warning","table":"person","column":"location","message":"Unsupported type geometry of the column person.location"}"#
+        );
+    }
+}
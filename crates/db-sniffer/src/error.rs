@@ -11,10 +11,39 @@ pub enum Error {
     IntrospectationError(String),
     #[error("Error connecting to the db: {0}")]
     DBConnectionError(String),
+    #[error("Invalid configuration: {0}")]
+    ConfigError(String),
+    #[error("Unsupported type {type} of the column {table}.{column}")]
+    UnsupportedTypeError {
+        table: String,
+        column: String,
+        r#type: String,
+    },
+    #[error("Error writing the generated files: {0}")]
+    GenerationError(String),
+    #[error("Invalid relation: {0}")]
+    InvalidRelationError(String),
+    #[error("Error introspecting the table {table}: {source}")]
+    TableError {
+        table: String,
+        #[source]
+        source: Box<Error>,
+    },
+    #[error("Error introspecting the routine {routine}: {source}")]
+    RoutineError {
+        routine: String,
+        #[source]
+        source: Box<Error>,
+    },
 }
 
 impl From<sqlx::Error> for Error {
     fn from(value: sqlx::Error) -> Self {
         Error::IntrospectationError(value.to_string())
     }
-}
\ No newline at end of file
+}
+impl From<tiberius::error::Error> for Error {
+    fn from(value: tiberius::error::Error) -> Self {
+        Error::IntrospectationError(value.to_string())
+    }
+}
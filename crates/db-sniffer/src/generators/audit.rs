@@ -0,0 +1,272 @@
+use crate::db_objects::{Column, ColumnType, Database, Table};
+use getset::{Getters, Setters};
+use std::mem;
+use std::str::FromStr;
+
+/// Meaning of an audit column
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AuditRole {
+    CreatedAt,
+    UpdatedAt,
+    CreatedBy,
+    UpdatedBy,
+}
+
+impl AuditRole {
+    /// Name of the property holding the column in the shared audit classes
+    pub fn property_name(&self) -> &'static str {
+        match self {
+            AuditRole::CreatedAt => "createdAt",
+            AuditRole::UpdatedAt => "updatedAt",
+            AuditRole::CreatedBy => "createdBy",
+            AuditRole::UpdatedBy => "updatedBy",
+        }
+    }
+
+    /// The value is written when the row is inserted and never updated
+    pub fn is_creation(&self) -> bool {
+        matches!(self, AuditRole::CreatedAt | AuditRole::CreatedBy)
+    }
+}
+
+/// How the audit columns are mapped
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum AuditMapping {
+    /// Mapped as any other column
+    #[default]
+    None,
+    /// Each entity maps its audit columns, timestamps with `@CreationTimestamp`/`@UpdateTimestamp`
+    Annotations,
+    /// The audit columns shared by all the audited tables are mapped by an `@Embedded Audit`
+    Embedded,
+    /// The audit columns shared by all the audited tables are mapped by an `AuditedEntity`
+    /// `@MappedSuperclass` extended by the entities
+    MappedSuperclass,
+}
+
+impl FromStr for AuditMapping {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(AuditMapping::None),
+            "annotations" => Ok(AuditMapping::Annotations),
+            "embedded" => Ok(AuditMapping::Embedded),
+            "superclass" => Ok(AuditMapping::MappedSuperclass),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Column names recognized as audit columns and how they are mapped
+#[derive(Getters, Setters, Clone, Debug)]
+pub struct AuditConvention {
+    #[getset(get = "pub", set = "pub")]
+    mapping: AuditMapping,
+    #[getset(get = "pub", set = "pub")]
+    created_at: Vec<String>,
+    #[getset(get = "pub", set = "pub")]
+    updated_at: Vec<String>,
+    #[getset(get = "pub", set = "pub")]
+    created_by: Vec<String>,
+    #[getset(get = "pub", set = "pub")]
+    updated_by: Vec<String>,
+}
+
+impl Default for AuditConvention {
+    fn default() -> Self {
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+
+        AuditConvention {
+            mapping: AuditMapping::None,
+            created_at: names(&["created", "created_at", "created_on", "creation_date"]),
+            updated_at: names(&[
+                "updated",
+                "updated_at",
+                "updated_on",
+                "modified",
+                "modified_at",
+                "last_modified",
+            ]),
+            created_by: names(&["created_by"]),
+            updated_by: names(&["updated_by", "modified_by"]),
+        }
+    }
+}
+
+impl AuditConvention {
+    /// Role of the column, if it follows the convention. Key, foreign key, computed and version
+    /// columns are never audit columns, and the timestamps must hold a date.
+    pub fn role(&self, table: &Table, column: &Column) -> Option<AuditRole> {
+        if self.mapping == AuditMapping::None
+            || !matches!(column.key(), crate::db_objects::KeyType::None)
+            || table.is_col_fk(column.name())
+            || column.is_computed()
+            || *column.version()
+        {
+            return None;
+        }
+
+        let is_named =
+            |names: &Vec<String>| names.iter().any(|n| n.eq_ignore_ascii_case(column.name()));
+        let is_date = matches!(
+            column.r#type(),
+            ColumnType::DateTime
+                | ColumnType::Date
+                | ColumnType::Timestamp
+                | ColumnType::DateTimeOffset
+        );
+
+        if is_date && is_named(&self.created_at) {
+            Some(AuditRole::CreatedAt)
+        } else if is_date && is_named(&self.updated_at) {
+            Some(AuditRole::UpdatedAt)
+        } else if is_named(&self.created_by) {
+            Some(AuditRole::CreatedBy)
+        } else if is_named(&self.updated_by) {
+            Some(AuditRole::UpdatedBy)
+        } else {
+            None
+        }
+    }
+
+    pub fn audit_columns<'a>(&self, table: &'a Table) -> Vec<(AuditRole, &'a Column)> {
+        let mut columns: Vec<(AuditRole, &Column)> = Vec::new();
+
+        for column in table.columns() {
+            if let Some(role) = self.role(table, column)
+                && !columns.iter().any(|(r, _)| *r == role)
+            {
+                columns.push((role, column));
+            }
+        }
+
+        columns
+    }
+
+    /// Audit columns present, with the same type, in every audited table. These are the ones
+    /// mapped by the shared embeddable or superclass, the first table giving their type.
+    pub fn shared_columns<'a>(&self, database: &'a Database) -> Vec<(AuditRole, &'a Column)> {
+        if !matches!(
+            self.mapping,
+            AuditMapping::Embedded | AuditMapping::MappedSuperclass
+        ) {
+            return vec![];
+        }
+
+        let audited_tables = database
+            .tables()
+            .iter()
+            .map(|t| self.audit_columns(t))
+            .filter(|c| !c.is_empty())
+            .collect::<Vec<Vec<(AuditRole, &Column)>>>();
+
+        let Some((first, others)) = audited_tables.split_first() else {
+            return vec![];
+        };
+
+        first
+            .iter()
+            .filter(|(role, column)| {
+                others.iter().all(|columns| {
+                    columns.iter().any(|(r, c)| {
+                        r == role
+                            && mem::discriminant(c.r#type()) == mem::discriminant(column.r#type())
+                    })
+                })
+            })
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_objects::{ColumnId, GenerationType, KeyType};
+
+    fn table(name: &str, columns: Vec<(&str, ColumnType)>) -> Table {
+        let mut table = Table::new(name);
+
+        table.add_column(Column::new(
+            ColumnId::new(name, "id"),
+            ColumnType::Integer(0),
+            false,
+            KeyType::Primary(GenerationType::AutoIncrement),
+        ));
+
+        for (column, r#type) in columns {
+            table.add_column(Column::new(
+                ColumnId::new(name, column),
+                r#type,
+                true,
+                KeyType::None,
+            ));
+        }
+
+        table
+    }
+
+    #[test]
+    fn test_audit_columns() {
+        let mut convention = AuditConvention::default();
+        convention.set_mapping(AuditMapping::Annotations);
+
+        let person = table(
+            "person",
+            vec![
+                ("Created_At", ColumnType::DateTime),
+                ("updated", ColumnType::Varchar(10)),
+                ("created_by", ColumnType::Varchar(20)),
+            ],
+        );
+
+        let roles = convention
+            .audit_columns(&person)
+            .into_iter()
+            .map(|(role, column)| (role, column.name()))
+            .collect::<Vec<(AuditRole, &str)>>();
+
+        assert_eq!(
+            roles,
+            vec![
+                (AuditRole::CreatedAt, "Created_At"),
+                (AuditRole::CreatedBy, "created_by")
+            ]
+        );
+
+        convention.set_mapping(AuditMapping::None);
+        assert!(convention.audit_columns(&person).is_empty());
+    }
+
+    #[test]
+    fn test_shared_columns() {
+        let mut convention = AuditConvention::default();
+        convention.set_mapping(AuditMapping::Embedded);
+
+        let mut database = Database::new("test_db");
+        database.add_table(table(
+            "person",
+            vec![
+                ("created_at", ColumnType::DateTime),
+                ("updated_at", ColumnType::DateTime),
+            ],
+        ));
+        database.add_table(table(
+            "invoice",
+            vec![
+                ("created", ColumnType::DateTime),
+                ("updated_at", ColumnType::Date),
+            ],
+        ));
+        database.add_table(table("country", vec![("name", ColumnType::Varchar(20))]));
+
+        let shared = convention
+            .shared_columns(&database)
+            .into_iter()
+            .map(|(role, _)| role)
+            .collect::<Vec<AuditRole>>();
+
+        assert_eq!(shared, vec![AuditRole::CreatedAt]);
+    }
+}
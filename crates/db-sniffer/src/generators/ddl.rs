@@ -0,0 +1,477 @@
+use crate::db_objects::{
+    Column, ColumnType, Dbms, GenerationType, KeyType, Relation, Table, Trigger,
+};
+use crate::generators::GenerationOptions;
+use crate::naming;
+use crate::sniffers::SniffResults;
+use std::fs;
+use std::path::PathBuf;
+
+pub struct DDLGenerator<'a> {
+    target_path: &'a PathBuf,
+    sniff_results: &'a SniffResults,
+    dbms: &'a Dbms,
+    quote_mixed_case: bool,
+}
+
+impl<'a> DDLGenerator<'a> {
+    pub fn new(sniff_results: &'a SniffResults, target_path: &'a PathBuf) -> Self {
+        Self::new_with_options(sniff_results, target_path, GenerationOptions::default())
+    }
+
+    pub fn new_with_options(
+        sniff_results: &'a SniffResults,
+        target_path: &'a PathBuf,
+        options: GenerationOptions,
+    ) -> Self {
+        let dbms = sniff_results
+            .metadata()
+            .as_ref()
+            .map(|m| m.dbms())
+            .unwrap_or(&Dbms::MySQL);
+
+        DDLGenerator {
+            target_path,
+            sniff_results,
+            dbms,
+            quote_mixed_case: *options.quote_mixed_case(),
+        }
+    }
+
+    pub fn generate(&self) {
+        let target_path = self.target_path;
+
+        if !target_path.exists()
+            && let Err(e) = fs::create_dir_all(target_path)
+        {
+            println!(
+                "Target path ({})  could not be created: {e}",
+                target_path.to_str().unwrap_or_default()
+            );
+            return;
+        }
+
+        let database = self.sniff_results.database();
+        let ddl_file_path = target_path.join(format!("{}.sql", database.name()));
+
+        fs::write(ddl_file_path, self.generate_ddl()).unwrap();
+    }
+
+    fn generate_ddl(&self) -> String {
+        let tables = self.sniff_results.database().tables();
+        let mut ddl = String::new();
+
+        for table in tables {
+            for column in table.columns() {
+                if let KeyType::Primary(GenerationType::Sequence(name, start, increment)) =
+                    column.key()
+                {
+                    ddl.push_str(&format!(
+                        "CREATE SEQUENCE {name} START WITH {start} INCREMENT BY {increment};\n\n"
+                    ));
+                }
+            }
+        }
+
+        for table in tables {
+            ddl.push_str(&self.generate_table_ddl(table));
+        }
+
+        for table in tables {
+            for relation in table.references() {
+                ddl.push_str(&generate_foreign_key_ddl(relation, self.dbms, self.quote_mixed_case));
+            }
+        }
+
+        for table in tables {
+            for trigger in table.triggers() {
+                ddl.push_str(&self.generate_trigger_ddl(trigger));
+            }
+        }
+
+        ddl
+    }
+
+    fn generate_trigger_ddl(&self, trigger: &Trigger) -> String {
+        match self.dbms {
+            Dbms::MySQL => format!(
+                "DELIMITER //\nCREATE TRIGGER {} {} ON {} FOR EACH ROW\n{}//\nDELIMITER ;\n\n",
+                trigger.name(),
+                trigger.fires_on(),
+                sql_name(trigger.table(), self.dbms, self.quote_mixed_case),
+                trigger.body().trim()
+            ),
+            // The body already is the whole CREATE TRIGGER statement
+            Dbms::Mssql => format!("{}\nGO\n\n", trigger.body().trim()),
+        }
+    }
+
+    fn generate_table_ddl(&self, table: &Table) -> String {
+        let mut definitions: Vec<String> = table
+            .columns()
+            .iter()
+            .map(|c| self.generate_column_ddl(c))
+            .collect();
+
+        let ids = table.ids();
+
+        if !ids.is_empty() {
+            let ids = ids
+                .iter()
+                .map(|c| sql_name(c.name(), self.dbms, self.quote_mixed_case))
+                .collect::<Vec<String>>()
+                .join(", ");
+            definitions.push(format!("PRIMARY KEY ({ids})"));
+        }
+
+        table
+            .columns()
+            .iter()
+            .filter(|c| matches!(c.key(), KeyType::Unique))
+            .for_each(|c| {
+                let name = sql_name(c.name(), self.dbms, self.quote_mixed_case);
+                definitions.push(format!("UNIQUE ({name})"))
+            });
+
+        let table_comment = match (self.dbms, table.comment()) {
+            (Dbms::MySQL, Some(comment)) => format!(" COMMENT = {}", sql_string(comment)),
+            _ => "".to_string(),
+        };
+
+        let mut ddl = format!(
+            "CREATE TABLE {} (\n    {}\n){table_comment};\n\n",
+            sql_name(table.name(), self.dbms, self.quote_mixed_case),
+            definitions.join(",\n    ")
+        );
+
+        // SQL Server stores the comments as extended properties of the objects
+        if let Dbms::Mssql = self.dbms {
+            if let Some(comment) = table.comment() {
+                ddl.push_str(&format!(
+                    "EXEC sp_addextendedproperty 'MS_Description', {}, 'SCHEMA', 'dbo', 'TABLE', {};\n",
+                    sql_string(comment),
+                    sql_string(table.name())
+                ));
+            }
+
+            for column in table.columns() {
+                if let Some(comment) = column.comment() {
+                    ddl.push_str(&format!(
+                        "EXEC sp_addextendedproperty 'MS_Description', {}, 'SCHEMA', 'dbo', 'TABLE', {}, 'COLUMN', {};\n",
+                        sql_string(comment),
+                        sql_string(table.name()),
+                        sql_string(column.name())
+                    ));
+                }
+            }
+
+            if !ddl.ends_with("\n\n") {
+                ddl.push('\n');
+            }
+        }
+
+        ddl
+    }
+
+    fn generate_column_ddl(&self, column: &Column) -> String {
+        let name = sql_name(column.name(), self.dbms, self.quote_mixed_case);
+
+        let mut ddl = match (column.computed(), self.dbms) {
+            (Some(computed), Dbms::MySQL) => format!(
+                "{name} {} GENERATED ALWAYS AS ({}) {}",
+                sql_type(column.r#type(), self.dbms),
+                computed.expression(),
+                if *computed.stored() { "STORED" } else { "VIRTUAL" }
+            ),
+            // SQL Server deduces the type of the computed columns
+            (Some(computed), Dbms::Mssql) => format!(
+                "{name} AS {}{}",
+                computed.expression(),
+                if *computed.stored() { " PERSISTED" } else { "" }
+            ),
+            (None, _) => format!("{name} {}", sql_type(column.r#type(), self.dbms)),
+        };
+
+        // Only the persisted computed columns can be declared NOT NULL in SQL Server
+        let nullability_allowed = match (column.computed(), self.dbms) {
+            (Some(computed), Dbms::Mssql) => *computed.stored(),
+            _ => true,
+        };
+
+        if column.not_nullable() && nullability_allowed {
+            ddl.push_str(" NOT NULL");
+        }
+
+        if let KeyType::Primary(generation) = column.key() {
+            ddl.push_str(&generation_ddl(generation, self.dbms));
+        }
+
+        if let (Dbms::MySQL, Some(comment)) = (self.dbms, column.comment()) {
+            ddl.push_str(&format!(" COMMENT {}", sql_string(comment)));
+        }
+
+        ddl
+    }
+}
+
+fn generate_foreign_key_ddl(relation: &Relation, dbms: &Dbms, quote_mixed_case: bool) -> String {
+    let from = relation
+        .from()
+        .iter()
+        .map(|c| sql_name(c.name(), dbms, quote_mixed_case))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let to = relation
+        .to()
+        .iter()
+        .map(|c| sql_name(c.name(), dbms, quote_mixed_case))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let inferred_comment = if *relation.inferred() {
+        "-- Inferred from the column names, not declared in the database\n"
+    } else {
+        ""
+    };
+
+    format!(
+        "{inferred_comment}ALTER TABLE {} ADD FOREIGN KEY ({from}) REFERENCES {} ({to});\n\n",
+        sql_name(relation.from()[0].table(), dbms, quote_mixed_case),
+        sql_name(relation.to()[0].table(), dbms, quote_mixed_case)
+    )
+}
+
+/// Name quoted when it is reserved or not a plain identifier, or on demand when it is mixed case
+fn sql_name(name: &str, dbms: &Dbms, quote_mixed_case: bool) -> String {
+    match dbms {
+        Dbms::MySQL => naming::quote_sql(name, "`", "`", quote_mixed_case),
+        Dbms::Mssql => naming::quote_sql(name, "[", "]", quote_mixed_case),
+    }
+}
+
+fn sql_type(column_type: &ColumnType, dbms: &Dbms) -> String {
+    match (column_type, dbms) {
+        (ColumnType::Integer(_), _) => "INT".to_string(),
+        (ColumnType::Text(_), Dbms::MySQL) => "TEXT".to_string(),
+        (ColumnType::Text(_), Dbms::Mssql) => "VARCHAR(MAX)".to_string(),
+        // SQL Server reports the (max) length as -1
+        (ColumnType::Char(len) | ColumnType::Varchar(len), Dbms::Mssql) if *len < 0 => {
+            "VARCHAR(MAX)".to_string()
+        }
+        (ColumnType::Char(len), _) => format!("CHAR({len})"),
+        (ColumnType::Varchar(len), _) => format!("VARCHAR({len})"),
+        (ColumnType::Float(_), _) => "FLOAT".to_string(),
+        (ColumnType::Double(_), Dbms::MySQL) => "DOUBLE".to_string(),
+        (ColumnType::Double(_), Dbms::Mssql) => "FLOAT(53)".to_string(),
+        (ColumnType::Date, _) => "DATE".to_string(),
+        (ColumnType::Time, _) => "TIME".to_string(),
+        (ColumnType::DateTime, _) => "DATETIME".to_string(),
+        (ColumnType::Timestamp, Dbms::MySQL) => "TIMESTAMP".to_string(),
+        (ColumnType::Timestamp, Dbms::Mssql) => "DATETIME2".to_string(),
+        (ColumnType::DateTimeOffset, Dbms::MySQL) => "DATETIME".to_string(),
+        (ColumnType::DateTimeOffset, Dbms::Mssql) => "DATETIMEOFFSET".to_string(),
+        (ColumnType::Boolean, Dbms::MySQL) => "BOOLEAN".to_string(),
+        (ColumnType::Boolean, Dbms::Mssql) => "BIT".to_string(),
+        (ColumnType::Blob(_), Dbms::MySQL) => "BLOB".to_string(),
+        (ColumnType::Blob(_), Dbms::Mssql) => "VARBINARY(MAX)".to_string(),
+        (ColumnType::Decimal(precision, scale), _) => format!("DECIMAL({precision}, {scale})"),
+        (ColumnType::Numeric(_), _) => "NUMERIC".to_string(),
+        (ColumnType::Uuid, Dbms::MySQL) => "CHAR(36)".to_string(),
+        (ColumnType::Uuid, Dbms::Mssql) => "UNIQUEIDENTIFIER".to_string(),
+        (ColumnType::RowVersion, Dbms::MySQL) => "BINARY(8)".to_string(),
+        (ColumnType::RowVersion, Dbms::Mssql) => "ROWVERSION".to_string(),
+    }
+}
+
+fn generation_ddl(generation: &GenerationType, dbms: &Dbms) -> String {
+    match (generation, dbms) {
+        (GenerationType::None, _) => "".to_string(),
+        (GenerationType::AutoIncrement | GenerationType::Identity(_, _), Dbms::MySQL) => {
+            " AUTO_INCREMENT".to_string()
+        }
+        (GenerationType::AutoIncrement, Dbms::Mssql) => " IDENTITY(1, 1)".to_string(),
+        (GenerationType::Identity(seed, increment), Dbms::Mssql) => {
+            format!(" IDENTITY({seed}, {increment})")
+        }
+        (GenerationType::Sequence(name, _, _), Dbms::MySQL) => {
+            format!(" DEFAULT nextval({name})")
+        }
+        (GenerationType::Sequence(name, _, _), Dbms::Mssql) => {
+            format!(" DEFAULT NEXT VALUE FOR {name}")
+        }
+        (GenerationType::Uuid, Dbms::MySQL) => " DEFAULT (UUID())".to_string(),
+        (GenerationType::Uuid, Dbms::Mssql) => " DEFAULT NEWID()".to_string(),
+        (GenerationType::Default(expr), _) => format!(" DEFAULT {expr}"),
+    }
+}
+
+/// SQL string literal with the quotes escaped
+fn sql_string(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_objects::{ColumnId, Computed, Database, Metadata, TriggerEvent, TriggerTiming};
+    use crate::sniffers::ConnectionParams;
+    use std::str::FromStr;
+
+    fn sniff_results(dbms: Dbms) -> SniffResults {
+        let mut table = Table::new("person");
+        table.set_comment(Some("People of the company".to_string()));
+
+        table.add_column(Column::new(
+            ColumnId::new("person", "id"),
+            ColumnType::Integer(0),
+            false,
+            KeyType::Primary(GenerationType::AutoIncrement),
+        ));
+
+        let mut name = Column::new(
+            ColumnId::new("person", "name"),
+            ColumnType::Varchar(50),
+            true,
+            KeyType::None,
+        );
+        name.set_comment(Some("Person's full name".to_string()));
+        table.add_column(name);
+
+        let mut database = Database::new("test_db");
+        database.add_table(table);
+
+        SniffResults::new(
+            Some(Metadata::new(dbms)),
+            database,
+            ConnectionParams::from_str("db://user:password@localhost:3306/test_db").unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_mysql_ddl_comments() {
+        let results = sniff_results(Dbms::MySQL);
+        let path = PathBuf::new();
+        let ddl = DDLGenerator::new(&results, &path).generate_ddl();
+
+        assert_eq!(
+            ddl,
+            "CREATE TABLE person (
+    id INT NOT NULL AUTO_INCREMENT,
+    name VARCHAR(50) COMMENT 'Person''s full name',
+    PRIMARY KEY (id)
+) COMMENT = 'People of the company';
+
+"
+        );
+    }
+
+    #[test]
+    fn test_mssql_ddl_comments() {
+        let results = sniff_results(Dbms::Mssql);
+        let path = PathBuf::new();
+        let ddl = DDLGenerator::new(&results, &path).generate_ddl();
+
+        assert_eq!(
+            ddl,
+            "CREATE TABLE person (
+    id INT NOT NULL IDENTITY(1, 1),
+    name VARCHAR(50),
+    PRIMARY KEY (id)
+);
+
+EXEC sp_addextendedproperty 'MS_Description', 'People of the company', 'SCHEMA', 'dbo', 'TABLE', 'person';
+EXEC sp_addextendedproperty 'MS_Description', 'Person''s full name', 'SCHEMA', 'dbo', 'TABLE', 'person', 'COLUMN', 'name';
+
+"
+        );
+    }
+
+    #[test]
+    fn test_mysql_trigger_ddl() {
+        let results = sniff_results(Dbms::MySQL);
+        let path = PathBuf::new();
+        let trigger = Trigger::new(
+            "person_bi",
+            "person",
+            TriggerTiming::Before,
+            vec![TriggerEvent::Insert],
+            "SET NEW.name = UPPER(NEW.name)",
+        );
+
+        assert_eq!(
+            DDLGenerator::new(&results, &path).generate_trigger_ddl(&trigger),
+            "DELIMITER //
+CREATE TRIGGER person_bi BEFORE INSERT ON person FOR EACH ROW
+SET NEW.name = UPPER(NEW.name)//
+DELIMITER ;
+
+"
+        );
+    }
+
+    #[test]
+    fn test_computed_column_ddl() {
+        let mut column = Column::new(
+            ColumnId::new("person", "full_name"),
+            ColumnType::Varchar(101),
+            false,
+            KeyType::None,
+        );
+        column.set_computed(Some(Computed::new("concat(name, ' ', surname)", true)));
+
+        let path = PathBuf::new();
+
+        let results = sniff_results(Dbms::MySQL);
+        assert_eq!(
+            DDLGenerator::new(&results, &path).generate_column_ddl(&column),
+            "full_name VARCHAR(101) GENERATED ALWAYS AS (concat(name, ' ', surname)) STORED NOT NULL"
+        );
+
+        column.set_computed(Some(Computed::new("([name]+' '+[surname])", false)));
+
+        let results = sniff_results(Dbms::Mssql);
+        assert_eq!(
+            DDLGenerator::new(&results, &path).generate_column_ddl(&column),
+            "full_name AS ([name]+' '+[surname])"
+        );
+    }
+
+    #[test]
+    fn test_quoted_names_ddl() {
+        let column = |name: &str| {
+            Column::new(
+                ColumnId::new("person", name),
+                ColumnType::Integer(0),
+                true,
+                KeyType::None,
+            )
+        };
+
+        let path = PathBuf::new();
+
+        let results = sniff_results(Dbms::MySQL);
+        let generator = DDLGenerator::new(&results, &path);
+        assert_eq!(generator.generate_column_ddl(&column("order")), "`order` INT");
+        assert_eq!(generator.generate_column_ddl(&column("FirstName")), "FirstName INT");
+        assert_eq!(generator.generate_column_ddl(&column("age")), "age INT");
+
+        let results = sniff_results(Dbms::Mssql);
+        let generator = DDLGenerator::new(&results, &path);
+        assert_eq!(generator.generate_column_ddl(&column("user")), "[user] INT");
+        assert_eq!(generator.generate_column_ddl(&column("unit price")), "[unit price] INT");
+        assert_eq!(generator.generate_column_ddl(&column("FirstName")), "FirstName INT");
+
+        // The mixed case names are also quoted on demand
+        let mut options = GenerationOptions::default();
+        options.set_quote_mixed_case(true);
+
+        let generator = DDLGenerator::new_with_options(&results, &path, options.clone());
+        assert_eq!(generator.generate_column_ddl(&column("FirstName")), "[FirstName] INT");
+        assert_eq!(generator.generate_column_ddl(&column("user")), "[user] INT");
+        assert_eq!(generator.generate_column_ddl(&column("age")), "age INT");
+
+        let results = sniff_results(Dbms::MySQL);
+        let generator = DDLGenerator::new_with_options(&results, &path, options);
+        assert_eq!(generator.generate_column_ddl(&column("FirstName")), "`FirstName` INT");
+    }
+}
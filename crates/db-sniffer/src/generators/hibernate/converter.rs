@@ -0,0 +1,250 @@
+use crate::db_objects::Database;
+use crate::diagnostics::{Diagnostic, DiagnosticKind};
+use crate::generators::{Conversion, TypeMapping};
+use dotjava::{Annotation, Class, Field, Interface, Method, Type, Visibility};
+
+/// `AttributeConverter`s implementing the conversions of the type overrides used by the
+/// columns, along with their class names. The columns whose converter can not be generated are
+/// reported in `diagnostics`.
+pub(super) fn generate_converters(
+    database: &Database,
+    type_mapping: &TypeMapping,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Vec<(String, String)> {
+    // Name, attribute and column types, and code of the generated converters
+    let mut converters: Vec<(String, String, String, String)> = Vec::new();
+
+    for column in database.tables().iter().flat_map(|t| t.columns()) {
+        let Some(type_override) = type_mapping.column_override(column) else {
+            continue;
+        };
+
+        // Type of the column in the database, without overrides
+        let column_type = column.r#type().to_java(*type_mapping.profile());
+
+        let (Some(conversion), Some(name)) = (
+            type_override.conversion(),
+            type_override.converter_name(&column_type),
+        ) else {
+            continue;
+        };
+
+        let Some(attribute_type) = type_override.java_type() else {
+            diagnostics.push(Diagnostic::new(
+                DiagnosticKind::UnsupportedConversion,
+                Some(column.table()),
+                Some(column.name()),
+                &format!(
+                    "The JSON conversion of {}.{} needs a Java type",
+                    column.table(),
+                    column.name()
+                ),
+            ));
+            continue;
+        };
+
+        let attribute_name = String::from(attribute_type.clone());
+        let column_name = String::from(column_type.clone());
+
+        if let Some((_, attribute, column_java, _)) = converters.iter().find(|c| c.0 == name) {
+            if *attribute != attribute_name || *column_java != column_name {
+                diagnostics.push(Diagnostic::new(
+                    DiagnosticKind::UnsupportedConversion,
+                    Some(column.table()),
+                    Some(column.name()),
+                    &format!(
+                        "The converter {name} of {}.{} converts {attribute} to {column_java}, \
+                         not {attribute_name} to {column_name}",
+                        column.table(),
+                        column.name()
+                    ),
+                ));
+            }
+
+            continue;
+        }
+
+        let converter = generate_converter(
+            &name,
+            type_mapping.converters_package(),
+            *conversion,
+            attribute_type,
+            column_type,
+        );
+
+        converters.push((name, attribute_name, column_name, converter));
+    }
+
+    converters
+        .into_iter()
+        .map(|(name, _, _, converter)| (name, converter))
+        .collect()
+}
+
+fn generate_converter(
+    name: &str,
+    package: &str,
+    conversion: Conversion,
+    attribute_type: Type,
+    column_type: Type,
+) -> String {
+    let attribute_name = String::from(attribute_type.clone());
+    let column_name = String::from(column_type.clone());
+
+    let (to_column, to_attribute) = match conversion {
+        Conversion::YesNo if column_name == "Character" => (
+            "return attribute ? 'Y' : 'N';".to_string(),
+            "return Character.toUpperCase(dbData) == 'Y';".to_string(),
+        ),
+        Conversion::YesNo => (
+            "return attribute ? \"Y\" : \"N\";".to_string(),
+            "return \"Y\".equalsIgnoreCase(dbData.trim());".to_string(),
+        ),
+        Conversion::Csv => (
+            "return String.join(\",\", attribute);".to_string(),
+            "return new ArrayList<>(Arrays.asList(dbData.split(\",\")));".to_string(),
+        ),
+        Conversion::Json => (
+            "try {\n    return mapper.writeValueAsString(attribute);\n} \
+             catch (JsonProcessingException e) {\n    \
+             throw new IllegalArgumentException(\"Could not write the JSON column\", e);\n}"
+                .to_string(),
+            format!(
+                "try {{\n    return mapper.readValue(dbData, new TypeReference<{attribute_name}>() {{}});\n}} \
+                 catch (JsonProcessingException e) {{\n    \
+                 throw new IllegalArgumentException(\"Could not read the JSON column\", e);\n}}"
+            ),
+        ),
+    };
+
+    let to_column = Method::new(
+        "convertToDatabaseColumn".to_string(),
+        column_type.clone(),
+        Some(Visibility::Public),
+        vec![(attribute_type.clone(), "attribute".to_string())],
+        Some(format!("if (attribute == null) {{\n    return null;\n}}\n\n{to_column}")),
+    );
+
+    let to_attribute = Method::new(
+        "convertToEntityAttribute".to_string(),
+        attribute_type.clone(),
+        Some(Visibility::Public),
+        vec![(column_type.clone(), "dbData".to_string())],
+        Some(format!("if (dbData == null) {{\n    return null;\n}}\n\n{to_attribute}")),
+    );
+
+    let fields = match conversion {
+        Conversion::Json => vec![Field::new(
+            "mapper".to_string(),
+            Type::new(
+                "ObjectMapper".to_string(),
+                "com.fasterxml.jackson.databind".to_string(),
+            ),
+            Some(Visibility::Private),
+            Some("new ObjectMapper()".to_string()),
+        )],
+        Conversion::YesNo | Conversion::Csv => vec![],
+    };
+
+    let mut java_class = Class::new(
+        name.to_string(),
+        package.to_string(),
+        fields,
+        vec![to_column, to_attribute],
+    );
+
+    match conversion {
+        Conversion::Csv => {
+            java_class.add_import("java.util.ArrayList".to_string());
+            java_class.add_import("java.util.Arrays".to_string());
+        }
+        Conversion::Json => {
+            java_class.add_import("com.fasterxml.jackson.core.JsonProcessingException".to_string());
+            java_class.add_import("com.fasterxml.jackson.core.type.TypeReference".to_string());
+        }
+        Conversion::YesNo => {}
+    }
+
+    let mut interface = Interface::new(
+        "AttributeConverter".to_string(),
+        "jakarta.persistence".to_string(),
+    );
+    interface.add_generic(attribute_type);
+    interface.add_generic(column_type);
+
+    java_class.set_doc(format!(
+        "Converts the {attribute_name} attributes to the {column_name} saved in the database"
+    ));
+    java_class.add_annotation(Annotation::new(
+        "Converter".to_string(),
+        "jakarta.persistence".to_string(),
+    ));
+    java_class.add_interface(interface);
+
+    java_class.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_objects::{Column, ColumnId, ColumnType, KeyType, Table};
+
+    #[test]
+    fn test_generate_converters() {
+        let mut type_mapping: TypeMapping = r#"
+            [types]
+            "char(1)" = { conversion = "yes_no" }
+
+            [columns]
+            "*.enabled" = { conversion = "yes_no" }
+            "*.tags" = { conversion = "csv", converter = "TagsConverter" }
+            "*.address" = { conversion = "json" }
+        "#
+        .parse()
+        .unwrap();
+        type_mapping.set_converters_package("com.example.model".to_string());
+
+        let mut person = Table::new("person");
+        for (name, r#type) in [
+            ("active", ColumnType::Char(1)),
+            ("deleted", ColumnType::Char(1)),
+            ("enabled", ColumnType::Varchar(1)),
+            ("tags", ColumnType::Varchar(255)),
+            ("address", ColumnType::Text(0)),
+        ] {
+            person.add_column(Column::new(
+                ColumnId::new("person", name),
+                r#type,
+                true,
+                KeyType::None,
+            ));
+        }
+
+        let mut database = Database::new("test_db");
+        database.add_table(person);
+
+        let mut diagnostics = Vec::new();
+        let converters = generate_converters(&database, &type_mapping, &mut diagnostics);
+        let names = converters.iter().map(|(n, _)| n.as_str()).collect::<Vec<&str>>();
+
+        // The JSON conversion without a Java type is skipped
+        assert_eq!(names, vec!["YesNoCharacterConverter", "YesNoConverter", "TagsConverter"]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(*diagnostics[0].kind(), DiagnosticKind::UnsupportedConversion);
+        assert_eq!(diagnostics[0].column().as_deref(), Some("address"));
+
+        let yes_no_character = &converters[0].1;
+        assert!(yes_no_character.contains(
+            "public class YesNoCharacterConverter implements AttributeConverter<Boolean, Character>"
+        ));
+        assert!(yes_no_character.contains("return Character.toUpperCase(dbData) == 'Y';"));
+
+        let yes_no = &converters[1].1;
+        assert!(yes_no.contains("AttributeConverter<Boolean, String>"));
+        assert!(yes_no.contains("return \"Y\".equalsIgnoreCase(dbData.trim());"));
+
+        let tags = &converters[2].1;
+        assert!(tags.contains("import java.util.List;"));
+        assert!(tags.contains("AttributeConverter<List<String>, String>"));
+    }
+}
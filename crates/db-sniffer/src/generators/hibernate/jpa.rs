@@ -1,10 +1,842 @@
-use std::path::PathBuf;
+use crate::db_objects::{
+    Column, ColumnType, Database, GenerationType, KeyType, ParameterMode, RelationType, Routine,
+    RoutineType, Table,
+};
+use crate::generators::{hibernate, keyless};
+use crate::generators::{
+    AuditMapping, AuditRole, FetchMode, GenerationOptions, RelationSettings, TypeMapping,
+};
+use crate::generators::hibernate::{Collection, RelationEnds, RelationProperty};
+use crate::naming::{self, NamingStrategy};
 use crate::sniffers::SniffResults;
+use dotjava::{Annotation, Class, Field, Interface, Type, Visibility};
+use std::fs;
+use std::path::PathBuf;
+
+pub struct JPAGenerator<'a> {
+    target_path: &'a PathBuf,
+    sniff_results: &'a SniffResults,
+    /// The sniffed database with the ids of the keyless tables
+    database: Database,
+    package: String,
+    src_path: PathBuf,
+    options: GenerationOptions,
+}
+
+impl<'a> JPAGenerator<'a> {
+    pub fn new(sniff_results: &'a SniffResults, target_path: &'a PathBuf) -> Option<Self> {
+        Self::new_with_options(sniff_results, target_path, GenerationOptions::default())
+    }
+
+    pub fn new_with_options(
+        sniff_results: &'a SniffResults,
+        target_path: &'a PathBuf,
+        mut options: GenerationOptions,
+    ) -> Option<Self> {
+        let src_path = hibernate::get_java_src_root(target_path);
+        let package = options
+            .package()
+            .clone()
+            .or_else(|| hibernate::get_java_package_name(target_path));
+
+        let src_path = if let Some(o) = src_path {
+            o
+        } else {
+            println!("src dir not found as a parent od the output dir");
+            return None;
+        };
+
+        let package = if let Some(o) = package {
+            o
+        } else {
+            println!("The package name couldn't be determined");
+            return None;
+        };
+
+        // The generated converters go along with the entities
+        if options.type_mapping().converters_package().is_empty() {
+            options
+                .type_mapping_mut()
+                .set_converters_package(package.clone());
+        }
+
+        // The keyless tables are reported by the diagnostics of the generation
+        let database = keyless::apply(sniff_results.database(), *options.keyless(), &mut Vec::new());
+
+        Some(JPAGenerator {
+            target_path,
+            sniff_results,
+            database,
+            package,
+            src_path,
+            options,
+        })
+    }
+
+    pub fn generate(&self) {
+        let target_path = self.target_path;
+
+        if !target_path.exists()
+            && let Err(e) = fs::create_dir_all(target_path)
+        {
+            println!(
+                "Target path ({})  could not be created: {e}",
+                target_path.to_str().unwrap_or_default()
+            );
+            return;
+        }
+
+        self.generate_tables_files(self.database.tables());
+
+        if let Err(e) = hibernate::generate_converters_files(
+            target_path,
+            &self.database,
+            self.options.type_mapping(),
+        ) {
+            println!("{e}");
+            return;
+        }
+
+        if let Some((class_name, audit_java)) = self.generate_audit_java() {
+            let audit_java_file_path = self.target_path.join(format!("{class_name}.java"));
+
+            fs::write(audit_java_file_path, audit_java).unwrap();
+        }
+
+        if !self.database.routines().is_empty() {
+            let routines_java = self.generate_routines_java();
+            let routines_java_file_path = self
+                .target_path
+                .join(format!("{}.java", hibernate::ROUTINES_CLASS_NAME));
+
+            fs::write(routines_java_file_path, routines_java).unwrap();
+        }
+
+        let conf_xml = self.generate_conf_xml();
+        let conf_file_path = self.src_path.join("hibernate.cfg.xml");
+
+        fs::write(conf_file_path, conf_xml).unwrap();
+    }
+
+    fn generate_conf_xml(&self) -> String {
+        let database = &self.database;
+
+        let mut class_names = database
+            .tables()
+            .iter()
+            .map(|t| self.options.naming().class_name(t.name()))
+            .collect::<Vec<String>>();
+
+        if !database.routines().is_empty() {
+            class_names.push(hibernate::ROUTINES_CLASS_NAME.to_string());
+        }
+
+        let mappings = class_names
+            .iter()
+            .map(|name| format!(r#"<mapping class="{}.{name}" />"#, self.package))
+            .collect::<Vec<String>>();
+
+        hibernate::generate_conf_xml(self.sniff_results, &mappings, &self.options)
+    }
+
+    fn generate_tables_files(&self, tables: &Vec<Table>) {
+        let relations = RelationEnds::new(
+            &self.database,
+            self.options.naming().as_ref(),
+            *self.options.bidirectional(),
+        );
+
+        for table in tables {
+            hibernate::warn_trigger_modified_columns(table);
+
+            let class_name = self.options.naming().class_name(table.name());
+
+            let table_java = self.generate_table_java(table, &relations);
+            let table_java_file_path = self.target_path.join(format!("{class_name}.java"));
+
+            fs::write(table_java_file_path, table_java).unwrap();
+
+            if table.ids().len() > 1 {
+                let composite_id_java = self.generate_composite_id(table);
+                let composite_id_java_file_path =
+                    self.target_path.join(format!("{class_name}Id.java"));
+
+                fs::write(composite_id_java_file_path, composite_id_java).unwrap();
+            }
+        }
+    }
+
+    fn generate_table_java(&self, table: &Table, relations: &RelationEnds) -> String {
+        let package = &self.package;
+        let class_name = self.options.naming().class_name(table.name());
+        let database = &self.database;
+        let quote_mixed_case = *self.options.quote_mixed_case();
+
+        let table_id = table.ids();
+        let immutable = hibernate::is_immutable(table, self.sniff_results, &self.options);
+        let id_class = immutable && table_id.len() > 1;
+        let properties = relations.of_table(table);
+
+        let audit = self.options.audit();
+        let shared_audit_columns = self.shared_audit_columns(table);
+
+        // The id of a table whose key is also a one-to-one foreign key is taken from the relation
+        let derived_id_property = properties.iter().find(|p| {
+            p.rel_owner
+                && matches!(p.relation.r#type(), RelationType::OneToOne)
+                && table_id.len() == 1
+                && p.relation.from().iter().any(|c| c.name() == table_id[0].name())
+        });
+
+        let mut fields: Vec<Field> = if table_id.len() == 1 || id_class {
+            table
+                .columns()
+                .iter()
+                .filter(|c| table_id.contains(c) || !table.is_col_fk(c.name()))
+                .filter(|c| !shared_audit_columns.iter().any(|(_, s)| s == c))
+                .map(|c| {
+                    let mut field = hibernate::generate_field(c, &self.options);
+
+                    if table_id.contains(&c) {
+                        field.add_annotation(jpa_annotation("Id"));
+
+                        if derived_id_property.is_none()
+                            && let KeyType::Primary(generation) = c.key()
+                        {
+                            generation_annotations(generation)
+                                .into_iter()
+                                .for_each(|a| field.add_annotation(a));
+                        }
+                    }
+
+                    let role = audit.role(table, c);
+
+                    column_annotations(c, role, self.options.type_mapping(), quote_mixed_case)
+                        .into_iter()
+                        .for_each(|a| field.add_annotation(a));
+                    field
+                })
+                .collect()
+        } else {
+            let mut fields: Vec<Field> = table
+                .columns()
+                .iter()
+                .filter(|c| !(table_id.contains(c) || table.is_col_fk(c.name())))
+                .filter(|c| !shared_audit_columns.iter().any(|(_, s)| s == c))
+                .map(|c| {
+                    let mut field = hibernate::generate_field(c, &self.options);
+                    let role = audit.role(table, c);
+
+                    column_annotations(c, role, self.options.type_mapping(), quote_mixed_case)
+                        .into_iter()
+                        .for_each(|a| field.add_annotation(a));
+                    field
+                })
+                .collect();
+
+            if !table_id.is_empty() {
+                let mut id_field = Field::new(
+                    "id".to_string(),
+                    Type::new(format!("{}Id", class_name), "".to_string()),
+                    Some(Visibility::Private),
+                    None,
+                );
+                id_field.add_annotation(jpa_annotation("EmbeddedId"));
+
+                fields.push(id_field);
+            }
+
+            fields
+        };
+
+        if *audit.mapping() == AuditMapping::Embedded && !shared_audit_columns.is_empty() {
+            let mut audit_field = Field::new(
+                "audit".to_string(),
+                Type::new(hibernate::AUDIT_CLASS_NAME.to_string(), "".to_string()),
+                Some(Visibility::Private),
+                None,
+            );
+            audit_field.add_annotation(jpa_annotation("Embedded"));
+
+            audit_overrides(&shared_audit_columns, quote_mixed_case)
+                .into_iter()
+                .for_each(|a| audit_field.add_annotation(a));
+
+            fields.push(audit_field);
+        }
+
+        for property in properties.iter() {
+            let field_type =
+                Type::new(self.options.naming().class_name(property.ref_table_name()), "".to_string());
+
+            let settings = self.options.relations().settings(table.name(), &property.name);
+            let collection = Collection::of(property, settings, database);
+
+            let mut field = hibernate::gen_rel_field(
+                property.name.clone(),
+                field_type,
+                collection.as_ref(),
+                self.options.type_mapping(),
+            );
+
+            let is_derived_id = derived_id_property.is_some_and(|p| std::ptr::eq(p, property));
+            let naming = self.options.naming().as_ref();
+
+            relation_annotations(
+                property,
+                table,
+                is_derived_id,
+                settings,
+                collection,
+                naming,
+                quote_mixed_case,
+            )
+            .into_iter()
+            .for_each(|a| field.add_annotation(a));
+
+            fields.push(field);
+        }
+
+        let methods = fields.iter().flat_map(|f| f.getters_setters()).collect();
+
+        let mut java_class = Class::new(class_name.clone(), package.clone(), fields, methods);
+
+        if let Some(doc) = hibernate::class_doc(table) {
+            java_class.set_doc(doc);
+        }
+
+        java_class.add_annotation(jpa_annotation("Entity"));
+        java_class.add_annotation(
+            jpa_annotation("Table")
+                .with_parameter("name", &sql_name(table.name(), quote_mixed_case)),
+        );
+
+        if id_class {
+            java_class.add_annotation(
+                jpa_annotation("IdClass").with_parameter("value", &format!("{class_name}Id.class")),
+            );
+        }
+
+        if immutable {
+            java_class.add_annotation(Annotation::new(
+                "Immutable".to_string(),
+                "org.hibernate.annotations".to_string(),
+            ));
+        }
+
+        if *audit.mapping() == AuditMapping::MappedSuperclass && !shared_audit_columns.is_empty() {
+            java_class.set_superclass(Type::new(
+                hibernate::AUDITED_ENTITY_CLASS_NAME.to_string(),
+                "".to_string(),
+            ));
+
+            audit_overrides(&shared_audit_columns, quote_mixed_case)
+                .into_iter()
+                .for_each(|a| java_class.add_annotation(a));
+        }
+
+        java_class.into()
+    }
+
+    /// Audit columns of the table mapped by the shared audit embeddable or superclass
+    fn shared_audit_columns<'t>(&self, table: &'t Table) -> Vec<(AuditRole, &'t Column)> {
+        let audit = self.options.audit();
+        let shared = audit.shared_columns(&self.database);
+
+        audit
+            .audit_columns(table)
+            .into_iter()
+            .filter(|(role, _)| shared.iter().any(|(r, _)| r == role))
+            .collect()
+    }
+
+    /// Embeddable or mapped superclass with the audit columns shared by the audited tables,
+    /// along with its class name
+    fn generate_audit_java(&self) -> Option<(String, String)> {
+        let audit = self.options.audit();
+        let shared = audit.shared_columns(&self.database);
+
+        let (class_name, annotation) = match audit.mapping() {
+            _ if shared.is_empty() => return None,
+            AuditMapping::Embedded => (hibernate::AUDIT_CLASS_NAME, "Embeddable"),
+            AuditMapping::MappedSuperclass => {
+                (hibernate::AUDITED_ENTITY_CLASS_NAME, "MappedSuperclass")
+            }
+            AuditMapping::None | AuditMapping::Annotations => return None,
+        };
+
+        let fields: Vec<Field> = shared
+            .iter()
+            .map(|(role, column)| {
+                let mut field = Field::new(
+                    role.property_name().to_string(),
+                    self.options.type_mapping().column_java_type(column),
+                    Some(Visibility::Private),
+                    None,
+                );
+
+                if let Some(annotation) = audit_annotation(*role) {
+                    field.add_annotation(annotation);
+                }
+
+                field
+            })
+            .collect();
+
+        let methods = fields.iter().flat_map(|f| f.getters_setters()).collect();
+
+        let mut java_class = Class::new(class_name.to_string(), self.package.clone(), fields, methods);
+
+        java_class.set_doc("Audit columns shared by the audited tables".to_string());
+        java_class.add_annotation(jpa_annotation(annotation));
+
+        Some((class_name.to_string(), java_class.into()))
+    }
+
+    /// Mapped superclass holding the named queries that call the routines of the database
+    fn generate_routines_java(&self) -> String {
+        let mut java_class = Class::new(
+            hibernate::ROUTINES_CLASS_NAME.to_string(),
+            self.package.clone(),
+            vec![],
+            vec![],
+        );
+
+        java_class.set_doc("Named queries calling the routines of the database".to_string());
+        java_class.add_annotation(jpa_annotation("MappedSuperclass"));
+
+        for routine in self.database.routines() {
+            java_class.add_annotation(routine_annotation(
+                routine,
+                self.sniff_results,
+                self.options.type_mapping(),
+            ));
+        }
+
+        java_class.into()
+    }
+
+    fn generate_composite_id(&self, table: &Table) -> String {
+        let package = &self.package;
+        let class_name = self.options.naming().class_name(table.name());
+
+        // The columns of an id class are mapped by the fields of the entity
+        let immutable = hibernate::is_immutable(table, self.sniff_results, &self.options);
+
+        let fields: Vec<Field> = table
+            .ids()
+            .iter()
+            .map(|c| {
+                let mut field = hibernate::generate_field(c, &self.options);
+
+                if !immutable {
+                    let quote_mixed_case = *self.options.quote_mixed_case();
+                    field.add_annotation(column_annotation(c, quote_mixed_case));
+                }
+
+                field
+            })
+            .collect();
+
+        let methods = fields.iter().flat_map(|f| f.getters_setters()).collect();
+
+        let mut java_class = Class::new(
+            format!("{}Id", class_name),
+            package.clone(),
+            fields,
+            methods,
+        );
+
+        if !immutable {
+            java_class.add_annotation(jpa_annotation("Embeddable"));
+        }
+
+        java_class.add_interface(Interface::new(
+            "Serializable".to_string(),
+            "java.io".to_string(),
+        ));
+
+        java_class.add_equals_method();
+        java_class.add_hash_code_method();
+
+        java_class.into()
+    }
+}
+
+fn jpa_annotation(name: &str) -> Annotation {
+    Annotation::new(name.to_string(), "jakarta.persistence".to_string())
+}
+
+/// Java string literal of a table or column name, delimited with escaped quotes when it is
+/// reserved or not a plain identifier, or on demand when it is mixed case
+fn sql_name(name: &str, quote_mixed_case: bool) -> String {
+    format!("\"{}\"", naming::quote_sql(name, "\\\"", "\\\"", quote_mixed_case))
+}
+
+/// Annotations mapping a column to a basic field
+fn column_annotations(
+    column: &Column,
+    audit_role: Option<AuditRole>,
+    type_mapping: &TypeMapping,
+    quote_mixed_case: bool,
+) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+
+    if let Some(converter) = type_mapping.column_converter_class(column) {
+        annotations.push(convert_annotation(&converter, type_mapping.converters_package()));
+    }
+
+    if let Some(annotation) = audit_role.and_then(audit_annotation) {
+        annotations.push(annotation);
+    }
+
+    if *column.version() {
+        annotations.push(jpa_annotation("Version"));
+    }
+
+    // The value is computed by the database, so it is read back after every write
+    if column.is_computed() {
+        annotations.push(
+            Annotation::new("Generated".to_string(), "org.hibernate.annotations".to_string())
+                .with_parameter("event", "{EventType.INSERT, EventType.UPDATE}")
+                .with_import("org.hibernate.generator.EventType"),
+        );
+    }
+
+    let mut column_annotation = column_annotation(column, quote_mixed_case);
+
+    if audit_role.is_some_and(|r| r.is_creation()) {
+        column_annotation.add_parameter("updatable".to_string(), "false".to_string());
+    }
+
+    annotations.push(column_annotation);
+    annotations
+}
+
+fn convert_annotation(converter: &str, package: &str) -> Annotation {
+    let (converter_package, converter_name) = converter.rsplit_once('.').unwrap_or(("", converter));
+
+    let annotation = jpa_annotation("Convert")
+        .with_parameter("converter", &format!("{converter_name}.class"));
+
+    // The generated converters are in the package of the entities
+    if converter_package.is_empty() || converter_package == package {
+        annotation
+    } else {
+        annotation.with_import(converter)
+    }
+}
+
+/// Hibernate annotation filling an audit timestamp
+fn audit_annotation(role: AuditRole) -> Option<Annotation> {
+    let name = match role {
+        AuditRole::CreatedAt => "CreationTimestamp",
+        AuditRole::UpdatedAt => "UpdateTimestamp",
+        AuditRole::CreatedBy | AuditRole::UpdatedBy => return None,
+    };
+
+    Some(Annotation::new(name.to_string(), "org.hibernate.annotations".to_string()))
+}
+
+/// Maps the properties of the shared audit class to the columns of a table
+fn audit_overrides(columns: &[(AuditRole, &Column)], quote_mixed_case: bool) -> Vec<Annotation> {
+    columns
+        .iter()
+        .map(|(role, column)| {
+            let updatable = if role.is_creation() { ", updatable = false" } else { "" };
+
+            jpa_annotation("AttributeOverride")
+                .with_parameter("name", &format!("\"{}\"", role.property_name()))
+                .with_parameter(
+                    "column",
+                    &format!("@Column(name = {}{updatable})", sql_name(column.name(), quote_mixed_case)),
+                )
+                .with_import("jakarta.persistence.Column")
+        })
+        .collect()
+}
+
+fn column_annotation(column: &Column, quote_mixed_case: bool) -> Annotation {
+    let mut annotation = jpa_annotation("Column")
+        .with_parameter("name", &sql_name(column.name(), quote_mixed_case));
+
+    if column.not_nullable() {
+        annotation.add_parameter("nullable".to_string(), "false".to_string());
+    }
+
+    if let KeyType::Unique = column.key() {
+        annotation.add_parameter("unique".to_string(), "true".to_string());
+    }
+
+    if column.is_database_generated() {
+        annotation.add_parameter("insertable".to_string(), "false".to_string());
+        annotation.add_parameter("updatable".to_string(), "false".to_string());
+    }
+
+    match column.r#type() {
+        ColumnType::Varchar(len) | ColumnType::Char(len) => {
+            annotation.add_parameter("length".to_string(), len.to_string());
+        }
+        ColumnType::Decimal(precision, scale) => {
+            annotation.add_parameter("precision".to_string(), precision.to_string());
+            annotation.add_parameter("scale".to_string(), scale.to_string());
+        }
+        _ => {}
+    }
+
+    annotation
+}
+
+fn generation_annotations(generation: &GenerationType) -> Vec<Annotation> {
+    match generation {
+        GenerationType::None | GenerationType::Default(_) => vec![],
+        GenerationType::AutoIncrement | GenerationType::Identity(_, _) => {
+            vec![
+                jpa_annotation("GeneratedValue")
+                    .with_parameter("strategy", "GenerationType.IDENTITY")
+                    .with_import("jakarta.persistence.GenerationType"),
+            ]
+        }
+        GenerationType::Sequence(name, start, increment) => {
+            vec![
+                jpa_annotation("GeneratedValue")
+                    .with_parameter("strategy", "GenerationType.SEQUENCE")
+                    .with_parameter("generator", &format!("\"{name}\""))
+                    .with_import("jakarta.persistence.GenerationType"),
+                jpa_annotation("SequenceGenerator")
+                    .with_parameter("name", &format!("\"{name}\""))
+                    .with_parameter("sequenceName", &format!("\"{name}\""))
+                    .with_parameter("initialValue", &start.to_string())
+                    .with_parameter("allocationSize", &increment.to_string()),
+            ]
+        }
+        GenerationType::Uuid => vec![Annotation::new(
+            "UuidGenerator".to_string(),
+            "org.hibernate.annotations".to_string(),
+        )],
+    }
+}
+
+fn routine_annotation(
+    routine: &Routine,
+    sniff_results: &SniffResults,
+    type_mapping: &TypeMapping,
+) -> Annotation {
+    let name = format!("\"{}\"", routine.name());
+
+    if let RoutineType::Function = routine.r#type() {
+        let sql = hibernate::routine_call_sql(routine, sniff_results);
+
+        return jpa_annotation("NamedNativeQuery")
+            .with_parameter("name", &name)
+            .with_parameter("query", &format!("\"{}\"", sql.replace('"', "\\\"")));
+    }
+
+    let mut annotation = jpa_annotation("NamedStoredProcedureQuery")
+        .with_parameter("name", &name)
+        .with_parameter("procedureName", &name)
+        .with_import("jakarta.persistence.StoredProcedureParameter")
+        .with_import("jakarta.persistence.ParameterMode");
+
+    if !routine.parameters().is_empty() {
+        let mut parameters = Vec::new();
+
+        for parameter in routine.parameters() {
+            let mode = match parameter.mode() {
+                ParameterMode::In => "ParameterMode.IN",
+                ParameterMode::Out => "ParameterMode.OUT",
+                ParameterMode::InOut => "ParameterMode.INOUT",
+            };
+
+            let java_type = type_mapping.java_type(parameter.r#type());
+            annotation = annotation.with_import(&java_type.package_required());
+
+            let parameter_annotation = jpa_annotation("StoredProcedureParameter")
+                .with_parameter("name", &format!("\"{}\"", parameter.name()))
+                .with_parameter("mode", mode)
+                .with_parameter("type", &format!("{}.class", String::from(java_type)));
+
+            parameters.push(String::from(parameter_annotation));
+        }
+
+        annotation.add_parameter("parameters".to_string(), format!("{{{}}}", parameters.join(", ")));
+    }
+
+    annotation
+}
+
+fn relation_annotations(
+    property: &RelationProperty,
+    table: &Table,
+    is_derived_id: bool,
+    settings: &RelationSettings,
+    collection: Option<Collection>,
+    naming: &dyn NamingStrategy,
+    quote_mixed_case: bool,
+) -> Vec<Annotation> {
+    let rel_type = property.rel_type();
+
+    let rel_annotation_name = match rel_type {
+        RelationType::OneToOne => "OneToOne",
+        RelationType::OneToMany => "OneToMany",
+        RelationType::ManyToOne => "ManyToOne",
+        RelationType::ManyToMany => "ManyToMany",
+    };
+
+    let mut relation_annotation = jpa_annotation(rel_annotation_name);
+
+    if !property.rel_owner {
+        let mapped_by = property
+            .other_end
+            .as_ref()
+            .expect("The inverse ends are only generated along with their owners");
+
+        relation_annotation.add_parameter("mappedBy".to_string(), format!("\"{mapped_by}\""));
+    }
+
+    // The inverse ends only set the fetch type when it is not the JPA default of the annotation,
+    // eager for the single valued ends and lazy for the collections
+    let lazy_by_default = matches!(rel_type, RelationType::OneToMany | RelationType::ManyToMany);
+
+    if property.rel_owner || *settings.lazy() != lazy_by_default {
+        let fetch_type = if *settings.lazy() {
+            "FetchType.LAZY"
+        } else {
+            "FetchType.EAGER"
+        };
+
+        relation_annotation = relation_annotation
+            .with_parameter("fetch", fetch_type)
+            .with_import("jakarta.persistence.FetchType");
+    }
+
+    match settings.cascade().as_slice() {
+        [] => {}
+        [cascade] => {
+            relation_annotation = relation_annotation
+                .with_parameter("cascade", cascade.to_jpa())
+                .with_import("jakarta.persistence.CascadeType");
+        }
+        cascade => {
+            let cascade: Vec<&str> = cascade.iter().map(|c| c.to_jpa()).collect();
+
+            relation_annotation = relation_annotation
+                .with_parameter("cascade", &format!("{{{}}}", cascade.join(", ")))
+                .with_import("jakarta.persistence.CascadeType");
+        }
+    }
+
+    if *settings.orphan_removal()
+        && matches!(rel_type, RelationType::OneToOne | RelationType::OneToMany)
+    {
+        relation_annotation.add_parameter("orphanRemoval".to_string(), "true".to_string());
+    }
+
+    let mut annotations = vec![relation_annotation];
+
+    if *settings.fetch() != FetchMode::Select {
+        annotations.push(
+            Annotation::new("Fetch".to_string(), "org.hibernate.annotations".to_string())
+                .with_parameter("value", settings.fetch().to_jpa())
+                .with_import("org.hibernate.annotations.FetchMode"),
+        );
+    }
+
+    match collection {
+        Some(Collection::List(column)) => annotations.push(
+            jpa_annotation("OrderColumn").with_parameter("name", &sql_name(column, quote_mixed_case)),
+        ),
+        Some(Collection::Map(key)) => annotations.push(jpa_annotation("MapKey").with_parameter(
+            "name",
+            &format!("\"{}\"", naming.field_name(key.name())),
+        )),
+        Some(Collection::Set | Collection::Bag) | None => {}
+    }
+
+    if !property.rel_owner {
+        return annotations;
+    }
+
+    if is_derived_id {
+        annotations.push(jpa_annotation("MapsId"));
+    }
+
+    // The key columns are already written by the id
+    let is_key = !is_derived_id
+        && property.relation.from().iter().any(|c| {
+            table
+                .column(c.name())
+                .is_some_and(|c| matches!(c.key(), KeyType::Primary(_)))
+        });
+
+    for (from, to) in property
+        .relation
+        .from()
+        .iter()
+        .zip(property.relation.to().iter())
+    {
+        let mut join_column = jpa_annotation("JoinColumn")
+            .with_parameter("name", &sql_name(from.name(), quote_mixed_case))
+            .with_parameter("referencedColumnName", &sql_name(to.name(), quote_mixed_case));
+
+        if is_key {
+            join_column.add_parameter("insertable".to_string(), "false".to_string());
+            join_column.add_parameter("updatable".to_string(), "false".to_string());
+        }
+
+        annotations.push(join_column);
+    }
+
+    annotations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_objects::{ColumnId, Relation};
+    use crate::naming::DefaultNaming;
+
+    #[test]
+    fn test_inverse_fetch_type() {
+        // Inverse end of a relation of the type, as seen from its owner
+        let annotation = |rel_type: RelationType, lazy: bool| {
+            let relation = Relation::new(
+                vec![ColumnId::new("person", "passport_id")],
+                vec![ColumnId::new("passport", "id")],
+                rel_type,
+            )
+            .unwrap();
+            let property = RelationProperty {
+                relation: &relation,
+                rel_owner: false,
+                name: "person".to_string(),
+                other_end: Some("passport".to_string()),
+            };
+
+            let mut settings = RelationSettings::default();
+            settings.set_lazy(lazy);
 
-pub struct JPAGenerator;
+            let annotations = relation_annotations(
+                &property,
+                &Table::new("passport"),
+                false,
+                &settings,
+                None,
+                &DefaultNaming::default(),
+                false,
+            );
+            String::from(annotations[0].clone())
+        };
 
-impl JPAGenerator {
-    fn generate_table_files(&self, _sniff_results: SniffResults, _target_path: PathBuf) {
-        todo!()
+        assert_eq!(
+            annotation(RelationType::OneToOne, true),
+            "@OneToOne(mappedBy = \"passport\", fetch = FetchType.LAZY)"
+        );
+        assert_eq!(annotation(RelationType::OneToOne, false), "@OneToOne(mappedBy = \"passport\")");
+        assert_eq!(annotation(RelationType::ManyToOne, true), "@OneToMany(mappedBy = \"passport\")");
+        assert_eq!(
+            annotation(RelationType::ManyToOne, false),
+            "@OneToMany(mappedBy = \"passport\", fetch = FetchType.EAGER)"
+        );
     }
 }
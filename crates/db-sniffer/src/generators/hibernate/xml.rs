@@ -1,9 +1,13 @@
 use crate::db_objects::{
     Column, ColumnId, ColumnType, Database, Dbms, GenerationType, KeyType, Relation, RelationType,
-    Table,
+    RoutineType, Table,
 };
-use crate::generators::hibernate;
-use crate::naming;
+use crate::generators::{hibernate, keyless};
+use crate::generators::{
+    AuditConvention, GenerationOptions, RelationMapping, RelationSettings, TypeMapping,
+};
+use crate::generators::hibernate::{Collection, RelationEnds, RelationProperty};
+use crate::naming::{self, NamingStrategy};
 use crate::sniffers::SniffResults;
 use dotjava::{Class, Field, Interface, Type, Visibility};
 use std::collections::HashMap;
@@ -16,14 +20,28 @@ use std::slice::Iter;
 pub struct XMLGenerator<'a> {
     target_path: &'a PathBuf,
     sniff_results: &'a SniffResults,
+    /// The sniffed database with the ids of the keyless tables
+    database: Database,
     package: String,
     src_path: PathBuf,
+    options: GenerationOptions,
 }
 
 impl<'a> XMLGenerator<'a> {
     pub fn new(sniff_results: &'a SniffResults, target_path: &'a PathBuf) -> Option<Self> {
+        Self::new_with_options(sniff_results, target_path, GenerationOptions::default())
+    }
+
+    pub fn new_with_options(
+        sniff_results: &'a SniffResults,
+        target_path: &'a PathBuf,
+        mut options: GenerationOptions,
+    ) -> Option<Self> {
         let src_path = hibernate::get_java_src_root(target_path);
-        let package = hibernate::get_java_package_name(target_path);
+        let package = options
+            .package()
+            .clone()
+            .or_else(|| hibernate::get_java_package_name(target_path));
 
         let src_path = if let Some(o) = src_path {
             o
@@ -48,29 +66,58 @@ impl<'a> XMLGenerator<'a> {
             )
         }
 
+        // The generated converters go along with the entities
+        if options.type_mapping().converters_package().is_empty() {
+            options
+                .type_mapping_mut()
+                .set_converters_package(package.clone());
+        }
+
+        // The keyless tables are reported by the diagnostics of the generation
+        let database = keyless::apply(sniff_results.database(), *options.keyless(), &mut Vec::new());
+
         Some(XMLGenerator {
             target_path,
             sniff_results,
+            database,
             package,
             src_path,
+            options,
         })
     }
 
     pub fn generate(&self) {
         let target_path = self.target_path;
-        let sniff_results = self.sniff_results;
 
-        if !target_path.exists() {
-            if let Err(e) = fs::create_dir_all(target_path) {
-                println!(
-                    "Target path ({})  could not be created: {e}",
-                    target_path.to_str().unwrap_or_default()
-                );
-                return;
-            };
+        if !target_path.exists()
+            && let Err(e) = fs::create_dir_all(target_path)
+        {
+            println!(
+                "Target path ({})  could not be created: {e}",
+                target_path.to_str().unwrap_or_default()
+            );
+            return;
+        }
+
+        self.generate_tables_files(self.database.tables());
+
+        if let Err(e) = hibernate::generate_converters_files(
+            target_path,
+            &self.database,
+            self.options.type_mapping(),
+        ) {
+            println!("{e}");
+            return;
         }
 
-        self.generate_tables_files(sniff_results.database().tables());
+        if !self.database.routines().is_empty() {
+            let routines_xml = self.generate_routines_xml();
+            let routines_file_path = self
+                .target_path
+                .join(format!("{}.hbm.xml", hibernate::ROUTINES_CLASS_NAME));
+
+            fs::write(routines_file_path, routines_xml).unwrap();
+        }
 
         let conf_xml = self.generate_conf_xml();
         let conf_file_path = self.src_path.join("hibernate.cfg.xml");
@@ -81,97 +128,55 @@ impl<'a> XMLGenerator<'a> {
     }
 
     fn generate_conf_xml(&self) -> String {
-        let conn_params = self.sniff_results.conn_params();
+        let database = &self.database;
 
-        let mapping_files = self
-            .sniff_results
-            .database()
+        let mut mapping_names = database
             .tables()
             .iter()
-            .map(|t| {
+            .map(|t| self.options.naming().class_name(t.name()))
+            .collect::<Vec<String>>();
+
+        if !database.routines().is_empty() {
+            mapping_names.push(hibernate::ROUTINES_CLASS_NAME.to_string());
+        }
+
+        let mappings = mapping_names
+            .iter()
+            .map(|name| {
                 format!(
-                    r#"<mapping resource="{}/{}.hbm.xml" />"#,
+                    r#"<mapping resource="{}/{name}.hbm.xml" />"#,
                     self.package.replace(".", "/"),
-                    naming::to_upper_camel_case(t.name())
                 )
             })
-            .collect::<Vec<String>>()
-            .join("\n         ");
-
-        let (dialect, driver, conn_str) = match self.sniff_results.metadata() {
-            Some(metadata) => match metadata.dbms() {
-                Dbms::Mssql => (
-                    "org.hibernate.dialect.SQLServerDialect",
-                    "com.microsoft.sqlserver.jdbc.SQLServerDriver",
-                    format!(
-                        "jdbc:sqlserver://{}:{};databaseName={};trustServerCertificate=true",
-                        conn_params.host().as_ref().unwrap(),
-                        conn_params.port().unwrap(),
-                        conn_params.dbname().as_ref().unwrap()
-                    ),
-                ),
-                Dbms::MySQL => (
-                    "org.hibernate.dialect.MySQLDialect",
-                    "com.mysql.cj.jdbc.Driver",
-                    format!(
-                        "jdbc:mysql://{}:{}/{}",
-                        conn_params.host().as_ref().unwrap(),
-                        conn_params.port().unwrap(),
-                        conn_params.dbname().as_ref().unwrap()
-                    ),
-                ),
-            },
-            None => ("", "", "".to_string()),
-        };
+            .collect::<Vec<String>>();
 
-        let properties = format!(
-            r#"
-        <property name="hibernate.dialect">{dialect}</property>
-        <property name="hibernate.connection.driver_class">{driver}</property>
-        <property name="hibernate.connection.url">{conn_str}</property>
-        <property name="hibernate.connection.username">{}</property>
-        <property name="hibernate.connection.password">{}</property>"#,
-            escape_xml_special_chars(conn_params.user().as_ref().unwrap()),
-            escape_xml_special_chars(conn_params.password().as_ref().unwrap()),
-        );
-
-        format!(
-            r#"<?xml version="1.0" encoding="UTF-8"?>
-<!DOCTYPE hibernate-configuration PUBLIC 
-"-//Hibernate/Hibernate Configuration DTD 3.0//EN" 
-"http://www.hibernate.org/dtd/hibernate-configuration-3.0.dtd">
-
-<hibernate-configuration>
-    <session-factory>
-        {properties}
-
-        <property name="hibernate.hbm2ddl.auto">validate</property>
-   
-        <!-- Mapping files -->
-        {mapping_files}
-    </session-factory>
-
-</hibernate-configuration>
-            "#
-        )
+        hibernate::generate_conf_xml(self.sniff_results, &mappings, &self.options)
     }
 
     fn generate_tables_files(&self, tables: &Vec<Table>) {
+        let relations = RelationEnds::new(
+            &self.database,
+            self.options.naming().as_ref(),
+            *self.options.bidirectional(),
+        );
+
         for table in tables {
-            let table_xml = self.generate_table_xml(table);
+            hibernate::warn_trigger_modified_columns(table);
+
+            let table_xml = self.generate_table_xml(table, &relations);
 
             let table_file_path = self.target_path.join(format!(
                 "{}.hbm.xml",
-                naming::to_upper_camel_case(table.name())
+                self.options.naming().class_name(table.name())
             ));
 
             fs::File::create(&table_file_path).unwrap();
             fs::write(table_file_path, table_xml).unwrap();
 
-            let table_java = self.generate_table_java(table);
+            let table_java = self.generate_table_java(table, &relations);
             let table_java_file_path = self.target_path.join(format!(
                 "{}.java",
-                naming::to_upper_camel_case(table.name())
+                self.options.naming().class_name(table.name())
             ));
 
             fs::File::create(&table_java_file_path).unwrap();
@@ -181,7 +186,7 @@ impl<'a> XMLGenerator<'a> {
                 let composite_id_java = self.generate_composite_id(table);
                 let composite_id_java_file_path = self.target_path.join(format!(
                     "{}Id.java",
-                    naming::to_upper_camel_case(table.name())
+                    self.options.naming().class_name(table.name())
                 ));
 
                 fs::File::create(&composite_id_java_file_path).unwrap();
@@ -190,8 +195,68 @@ impl<'a> XMLGenerator<'a> {
         }
     }
 
-    fn generate_table_xml(&self, table: &Table) -> String {
+    fn generate_routines_xml(&self) -> String {
+        let type_mapping = self.options.type_mapping();
+        let mut queries = String::new();
+
+        for routine in self.database.routines() {
+            let callable = match routine.r#type() {
+                RoutineType::Procedure => r#" callable="true""#,
+                RoutineType::Function => "",
+            };
+
+            let return_scalars = match routine.return_type() {
+                Some(return_type) => vec![("result", return_type)],
+                None => routine
+                    .result_columns()
+                    .iter()
+                    .map(|(name, r#type)| (name.as_str(), r#type))
+                    .collect(),
+            };
+
+            queries.push_str(&format!(
+                r#"
+  <sql-query name="{}"{callable}>"#,
+                routine.name()
+            ));
+
+            for (column, r#type) in return_scalars {
+                queries.push_str(&format!(
+                    r#"
+    <return-scalar column="{column}" type="{}"/>"#,
+                    type_mapping.hibernate_type(r#type)
+                ));
+            }
+
+            queries.push_str(&format!(
+                r#"
+    {}
+  </sql-query>"#,
+                hibernate::escape_xml_special_chars(&hibernate::routine_call_sql(
+                    routine,
+                    self.sniff_results
+                ))
+            ));
+        }
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE hibernate-mapping PUBLIC
+    "-//Hibernate/Hibernate Mapping DTD 3.0//EN"
+    "http://www.hibernate.org/dtd/hibernate-mapping-3.0.dtd">
+
+<hibernate-mapping>{queries}
+</hibernate-mapping>
+"#
+        )
+    }
+
+    fn generate_table_xml(&self, table: &Table, relations: &RelationEnds) -> String {
         let package = &self.package;
+        let type_mapping = self.options.type_mapping();
+        let immutable = hibernate::is_immutable(table, self.sniff_results, &self.options);
+        let naming = self.options.naming().as_ref();
+        let quote_mixed_case = *self.options.quote_mixed_case();
 
         let xml = format!(
             r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -200,23 +265,72 @@ impl<'a> XMLGenerator<'a> {
     "http://www.hibernate.org/dtd/hibernate-mapping-3.0.dtd">
 
 <hibernate-mapping>
-  <class name="{package}.{}" table="{}">
-{}
+  <class name="{package}.{}" table="{}"{}>{}
+{}{}
 {}
 {}
   </class>
 </hibernate-mapping>
         "#,
-            naming::to_upper_camel_case(table.name()),
-            table.name(),
-            generate_id_xml(table, package),
-            generate_properties_xml(table),
-            generate_references_to_xml(table, package, self.sniff_results.database())
+            naming.class_name(table.name()),
+            sql_name(table.name(), quote_mixed_case),
+            if immutable { r#" mutable="false""# } else { "" },
+            generate_comment_xml(table.comment()) + &generate_triggers_xml(table),
+            generate_id_xml(
+                table,
+                package,
+                immutable,
+                relations,
+                naming,
+                type_mapping,
+                quote_mixed_case
+            ),
+            generate_version_xml(table, naming, type_mapping, quote_mixed_case),
+            generate_properties_xml(
+                table,
+                self.options.audit(),
+                naming,
+                type_mapping,
+                quote_mixed_case
+            ),
+            generate_references_to_xml(table, package, &self.database, relations, &self.options)
         );
 
         return xml;
 
-        fn generate_id_xml(table: &Table, package: &str) -> String {
+        fn generate_triggers_xml(table: &Table) -> String {
+            table
+                .triggers()
+                .iter()
+                .map(|t| {
+                    format!(
+                        "\n    <!-- Trigger {} ({}) -->",
+                        t.name(),
+                        t.fires_on()
+                    )
+                })
+                .collect()
+        }
+
+        fn generate_comment_xml(comment: &Option<String>) -> String {
+            match comment {
+                Some(comment) => format!(
+                    "\n    <comment>{}</comment>",
+                    hibernate::escape_xml_special_chars(comment)
+                ),
+                None => "".to_string(),
+            }
+        }
+
+        fn generate_id_xml(
+            table: &Table,
+            package: &str,
+            immutable: bool,
+            relations: &RelationEnds,
+            naming: &dyn NamingStrategy,
+            type_mapping: &TypeMapping,
+            quote_mixed_case: bool,
+        ) -> String {
             let id_columns = table.ids();
             let mut result = "    <!-- Id -->".to_string();
 
@@ -227,49 +341,34 @@ impl<'a> XMLGenerator<'a> {
             if id_columns.len() == 1 {
                 let id = id_columns[0];
 
-                let ref_col = table
+                let ref_relation = table
                     .references()
                     .iter()
                     .filter(|&r| {
                         matches!(r.r#type(), RelationType::OneToOne)
                     })
-                    .filter_map(|r| {
-                        if r.from()
+                    .filter(|r| {
+                        r.from()
                             .iter()
                             .filter(|c| c.name() == id.name())
-                            .collect::<Vec<&ColumnId>>()
-                            .len()
+                            .count()
                             == 1
-                        {
-                            Some(r.to().first().unwrap())
-                        } else {
-                            None
-                        }
                     })
-                    .collect::<Vec<&ColumnId>>();
+                    .collect::<Vec<&Relation>>();
 
-                let generator = if let Some(col) = ref_col.first() {
+                let generator = if let Some(relation) = ref_relation.first() {
                     format!(
                         r#"
       <generator class="foreign">
           <param name="property">{}</param>
       </generator>"#,
-                        naming::to_lower_camel_case(col.table())
+                        relations.owner_name(relation)
                     )
                 } else {
-                    let gen_class = match id.key() {
-                        KeyType::Primary(a) => match a {
-                            GenerationType::None => "assigned",
-                            GenerationType::AutoIncrement => "identity",
-                        },
+                    match id.key() {
+                        KeyType::Primary(generation) => generate_generator_xml(generation),
                         _ => panic!("This section should not be reached"),
-                    };
-
-                    format!(
-                        r#"
-      <generator class="{}"/>"#,
-                        gen_class
-                    )
+                    }
                 };
 
                 result = result.add(&format!(
@@ -277,15 +376,22 @@ impl<'a> XMLGenerator<'a> {
     <id name="{}" type="{}">
       {}{generator}
     </id>"#,
-                    naming::to_lower_camel_case(id.name()),
-                    id.r#type().to_hibernate(),
-                    &generate_column_xml(id)
+                    naming.field_name(id.name()),
+                    type_mapping.column_hibernate_type(id),
+                    &generate_column_xml(id, quote_mixed_case)
                 ));
             } else {
+                // The key properties of an immutable entity are its own fields
+                let composite_id = if immutable {
+                    r#"mapped="true""#
+                } else {
+                    r#"name="id""#
+                };
+
                 result = result.add(&format!(
                     r#"
-    <composite-id name="id" class="{package}.{}Id">"#,
-                    naming::to_upper_camel_case(table.name()),
+    <composite-id {composite_id} class="{package}.{}Id">"#,
+                    naming.class_name(table.name()),
                 ));
 
                 for id_column in id_columns {
@@ -295,9 +401,9 @@ impl<'a> XMLGenerator<'a> {
         {}
       </key-property>
 "#,
-                        naming::to_lower_camel_case(id_column.name()),
-                        id_column.r#type().to_hibernate(),
-                        &generate_column_xml(id_column)
+                        naming.field_name(id_column.name()),
+                        type_mapping.column_hibernate_type(id_column),
+                        &generate_column_xml(id_column, quote_mixed_case)
                     ));
                 }
 
@@ -307,7 +413,84 @@ impl<'a> XMLGenerator<'a> {
             result
         }
 
-        fn generate_properties_xml(table: &Table) -> String {
+        fn generate_version_xml(
+            table: &Table,
+            naming: &dyn NamingStrategy,
+            type_mapping: &TypeMapping,
+            quote_mixed_case: bool,
+        ) -> String {
+            let Some(column) = table.version_column() else {
+                return "".to_string();
+            };
+
+            let name = naming.field_name(column.name());
+
+            match column.r#type() {
+                ColumnType::DateTime | ColumnType::Timestamp => format!(
+                    r#"
+
+    <!-- Version -->
+    <timestamp name="{name}" column="{}"/>"#,
+                    sql_name(column.name(), quote_mixed_case)
+                ),
+                // The database changes the rowversion on every write
+                ColumnType::RowVersion => format!(
+                    r#"
+
+    <!-- Version -->
+    <version name="{name}" type="binary" generated="always" insert="false">
+      {}
+    </version>"#,
+                    generate_column_xml(column, quote_mixed_case)
+                ),
+                _ => format!(
+                    r#"
+
+    <!-- Version -->
+    <version name="{name}" type="{}">
+      {}
+    </version>"#,
+                    type_mapping.column_hibernate_type(column),
+                    generate_column_xml(column, quote_mixed_case)
+                ),
+            }
+        }
+
+        fn generate_generator_xml(generation: &GenerationType) -> String {
+            match generation {
+                GenerationType::None => r#"
+      <generator class="assigned"/>"#
+                    .to_string(),
+                GenerationType::AutoIncrement | GenerationType::Identity(_, _) => r#"
+      <generator class="identity"/>"#
+                    .to_string(),
+                GenerationType::Sequence(name, start, increment) => format!(
+                    r#"
+      <generator class="sequence">
+        <param name="sequence_name">{name}</param>
+        <param name="initial_value">{start}</param>
+        <param name="increment_size">{increment}</param>
+      </generator>"#
+                ),
+                GenerationType::Uuid => r#"
+      <generator class="uuid2"/>"#
+                    .to_string(),
+                GenerationType::Default(expr) => format!(
+                    r#"
+      <!-- Database default: {} -->
+      <generator class="assigned"/>"#,
+                    hibernate::escape_xml_special_chars(expr).replace("--", "- -")
+                ),
+            }
+        }
+
+        fn generate_properties_xml(
+            table: &Table,
+            audit: &AuditConvention,
+            naming: &dyn NamingStrategy,
+            type_mapping: &TypeMapping,
+            quote_mixed_case: bool,
+        ) -> String {
             let mut result = "\n    <!-- Properties -->".to_string();
 
             for column in table.columns() {
@@ -315,28 +498,44 @@ impl<'a> XMLGenerator<'a> {
                     continue;
                 }
 
-                if table.is_col_fk(column.name()) {
+                if table.is_col_fk(column.name()) || *column.version() {
                     continue;
                 }
 
+                for trigger in table.triggers_modifying(column.name()) {
+                    result.push_str(&format!(
+                        "\n    <!-- Modified by the trigger {} -->",
+                        trigger.name()
+                    ));
+                }
+
+                // The database computes the value, so it is read back after every write
+                let read_only = if column.is_database_generated() {
+                    r#" insert="false" update="false" generated="always""#
+                } else if audit.role(table, column).is_some_and(|r| r.is_creation()) {
+                    r#" update="false""#
+                } else {
+                    ""
+                };
+
                 result = result.add(&format!(
                     r#"
-    <property name="{}" type="{}">
+    <property name="{}" type="{}"{read_only}>
       {}
     </property>"#,
-                    naming::to_lower_camel_case(column.name()),
-                    column.r#type().to_hibernate(),
-                    &generate_column_xml(column)
+                    naming.field_name(column.name()),
+                    type_mapping.column_hibernate_type(column),
+                    &generate_column_xml(column, quote_mixed_case)
                 ));
             }
 
             result
         }
 
-        fn generate_column_xml(column: &Column) -> String {
+        fn generate_column_xml(column: &Column, quote_mixed_case: bool) -> String {
             let mut column_str = format!(
                 r#"<column name="{}"{}{}"#,
-                column.name(),
+                sql_name(column.name(), quote_mixed_case),
                 if column.not_nullable() {
                     " not-null=\"true\""
                 } else {
@@ -364,196 +563,245 @@ impl<'a> XMLGenerator<'a> {
 
             column_str.push_str(&col_length);
 
-            column_str.push_str("/>");
+            match column.comment() {
+                Some(comment) => column_str.push_str(&format!(
+                    ">\n        <comment>{}</comment>\n      </column>",
+                    hibernate::escape_xml_special_chars(comment)
+                )),
+                None => column_str.push_str("/>"),
+            }
 
             column_str
         }
 
-        fn generate_multi_column_xml(columns: &Vec<&Column>) -> String {
+        fn generate_multi_column_xml(columns: &Vec<&Column>, quote_mixed_case: bool) -> String {
             let mut result = "".to_string();
 
             for column in columns {
-                result = result.add(&generate_column_xml(column)).add("\n        ");
+                result = result
+                    .add(&generate_column_xml(column, quote_mixed_case))
+                    .add("\n        ");
             }
 
             result
         }
 
-        // TODO: This many parameters makes this function ugly af
-        fn generate_references_to_xml(table: &Table, package: &str, database: &Database) -> String {
-            let mut used_names = HashMap::new();
+        fn generate_references_to_xml(
+            table: &Table,
+            package: &str,
+            database: &Database,
+            relations: &RelationEnds,
+            options: &GenerationOptions,
+        ) -> String {
             let mut result = "\n    <!-- References -->".to_string();
+            let properties = relations.of_table(table);
+
+            properties.iter().filter(|p| p.rel_owner).for_each(|p| {
+                // The key columns are already written by the id
+                let is_key = matches!(
+                    table
+                        .column(p.relation.from()[0].name())
+                        .expect("Should exists")
+                        .key(),
+                    KeyType::Primary(_)
+                );
 
-            table.references().iter().for_each(|r| {
-                if let KeyType::Primary(_) = table
-                    .column(r.from()[0].name())
-                    .expect("Should exists")
-                    .key()
-                {
-                    result.push_str(&generate_relation_xml(
-                        r,
-                        package,
-                        database,
-                        true,
-                        false,
-                        false,
-                        &mut used_names,
-                    ));
-                } else {
-                    result.push_str(&generate_relation_xml(
-                        r,
-                        package,
-                        database,
-                        true,
-                        true,
-                        true,
-                        &mut used_names,
-                    ));
-                };
+                let settings = options.relations().settings(table.name(), &p.name);
+
+                result.push_str(&generate_relation_xml(
+                    p, package, database, settings, options, !is_key,
+                ));
             });
 
             result.push_str("\n    <!-- Referenced by -->");
 
-            database
-                .table_referenced_by(table.name())
-                .iter()
-                .for_each(|r| {
-                    result.push_str(&generate_relation_xml(
-                        r,
-                        package,
-                        database,
-                        false,
-                        true,
-                        true,
-                        &mut used_names,
-                    ));
-                });
+            properties.iter().filter(|p| !p.rel_owner).for_each(|p| {
+                let settings = options.relations().settings(table.name(), &p.name);
+
+                result.push_str(&generate_relation_xml(
+                    p, package, database, settings, options, true,
+                ));
+            });
 
             result
         }
 
-        // TODO: This many parameters makes this function ugly af
         fn generate_relation_xml(
-            relation: &Relation,
+            property: &RelationProperty,
             package: &str,
             database: &Database,
-            rel_owner: bool,
-            insert: bool,
-            update: bool,
-            used_names: &mut HashMap<String, i32>,
+            settings: &RelationSettings,
+            options: &GenerationOptions,
+            writable: bool,
         ) -> String {
+            let naming = options.naming().as_ref();
+            let type_mapping = options.type_mapping();
+            let quote_mixed_case = *options.quote_mixed_case();
+
             let package = if package.is_empty() {
                 "".to_string()
             } else { 
                 format!("{}.", package)
             };
-            
+
+            let relation = property.relation;
+            let rel_owner = property.rel_owner;
+
             let cols: Vec<&Column> = relation
                 .from()
                 .iter()
                 .map(|c| database.column(c).unwrap())
                 .collect();
 
-            let (ref_table_name, rel_type) = if rel_owner {
-                (relation.to()[0].table(), relation.r#type())
+            let ref_table_name = property.ref_table_name();
+            let rel_type = property.rel_type();
+            let name = &property.name;
+
+            let inferred_comment = if *relation.inferred() {
+                "\n    <!-- Inferred from the column names, not declared in the database -->"
             } else {
-                (relation.from()[0].table(), &relation.r#type().inverse())
+                ""
             };
 
-            let ref_table_name_count = if let Some(count) = used_names.get_mut(ref_table_name) {
-                *count += 1;
-                format!("{}{}", ref_table_name, count)
-            } else {
-                used_names.insert(ref_table_name.to_string(), 1);
-                ref_table_name.to_string()
+            // Otherwise the one-to-one relation is a unique foreign key
+            let shared_key = cols.iter().all(|c| matches!(c.key(), KeyType::Primary(_)));
+
+            let collection = Collection::of(property, settings, database);
+            let cascade = generate_cascade_xml(settings, collection.is_some());
+            let fetch = settings.fetch().to_xml(collection.is_some());
+            let lazy = match (*settings.lazy(), collection.is_some()) {
+                (true, true) => "true",
+                (true, false) => "proxy",
+                (false, _) => "false",
             };
 
-            match rel_type {
+            let relation_xml = match rel_type {
+                RelationType::OneToOne if shared_key || !rel_owner => {
+                    // The inverse end of a unique foreign key is joined by the owner property
+                    let property_ref = match &property.other_end {
+                        Some(owner) if !shared_key => format!(r#" property-ref="{owner}""#),
+                        _ => "".to_string(),
+                    };
+
+                    format!(
+                        r#"
+    <one-to-one name="{}" class="{package}{}"{property_ref}{cascade} lazy="{lazy}" fetch="{fetch}" constrained="{rel_owner}"/>"#,
+                        name,
+                        naming.class_name(ref_table_name)
+                    )
+                }
                 RelationType::OneToOne => {
                     format!(
                         r#"
-    <one-to-one name="{}" class="{package}{}" lazy="proxy" constrained="{rel_owner}"/>"#,
-                        naming::to_lower_camel_case(&ref_table_name_count),
-                        naming::to_upper_camel_case(ref_table_name)
+    <many-to-one name="{}" class="{package}{}" unique="true"{cascade} lazy="{lazy}" fetch="{fetch}">
+      {}
+    </many-to-one>"#,
+                        name,
+                        naming.class_name(ref_table_name),
+                        generate_multi_column_xml(&cols, quote_mixed_case)
                     )
                 }
-                RelationType::OneToMany => {
+                RelationType::OneToMany | RelationType::ManyToMany => {
+                    let (element, inverse) = if rel_type == RelationType::OneToMany {
+                        ("one-to-many", r#" inverse="true""#)
+                    } else {
+                        ("many-to-many", "")
+                    };
+
+                    let (tag, index) = match collection {
+                        Some(Collection::Bag) => ("bag", "".to_string()),
+                        Some(Collection::List(column)) => (
+                            "list",
+                            format!(
+                                "\n      <list-index column=\"{}\"/>",
+                                sql_name(column, quote_mixed_case)
+                            ),
+                        ),
+                        Some(Collection::Map(key)) => (
+                            "map",
+                            format!(
+                                "\n      <map-key column=\"{}\" type=\"{}\"/>",
+                                sql_name(key.name(), quote_mixed_case),
+                                type_mapping.column_hibernate_type(key)
+                            ),
+                        ),
+                        Some(Collection::Set) | None => ("set", "".to_string()),
+                    };
+
                     format!(
                         r#"
-    <set name="{}s" table="{}" inverse="true" lazy="true" fetch="select">
+    <{tag} name="{}" table="{}"{inverse}{cascade} lazy="{lazy}" fetch="{fetch}">
       <key>
         {}
-      </key>
-      <one-to-many class="{package}{}" />
-    </set>"#,
-                        naming::to_lower_camel_case(&ref_table_name_count),
-                        ref_table_name_count,
-                        generate_multi_column_xml(&cols),
-                        naming::to_upper_camel_case(ref_table_name)
+      </key>{index}
+      <{element} class="{package}{}" />
+    </{tag}>"#,
+                        name,
+                        sql_name(ref_table_name, quote_mixed_case),
+                        generate_multi_column_xml(&cols, quote_mixed_case),
+                        naming.class_name(ref_table_name)
                     )
                 }
                 RelationType::ManyToOne => {
-                    let insert_update_str = if !insert && !update {
-                        " insert=\"false\" update=\"false\""
-                    } else if !insert {
-                        " insert=\"false\""
-                    } else if !update {
-                        " update=\"false\""
-                    } else {
+                    let insert_update_str = if writable {
                         ""
+                    } else {
+                        " insert=\"false\" update=\"false\""
                     };
 
                     format!(
                         r#"
-    <many-to-one name="{}" class="{package}{}" {insert_update_str} fetch="select">
+    <many-to-one name="{}" class="{package}{}"{insert_update_str}{cascade} lazy="{lazy}" fetch="{fetch}">
       {}
     </many-to-one>"#,
-                        naming::to_lower_camel_case(&ref_table_name_count),
-                        naming::to_upper_camel_case(ref_table_name),
-                        generate_multi_column_xml(&cols)
-                    )
-                }
-                RelationType::ManyToMany => {
-                    format!(
-                        r#"
-    <set name="{}s" table="{}" lazy="true" fetch="select">
-      <key>
-        {}
-      </key>
-      <many-to-many class="{package}.{}" />
-    </set>
-    "#,
-                        naming::to_lower_camel_case(&ref_table_name_count),
-                        ref_table_name_count,
-                        generate_multi_column_xml(&cols),
-                        naming::to_upper_camel_case(ref_table_name),
+                        name,
+                        naming.class_name(ref_table_name),
+                        generate_multi_column_xml(&cols, quote_mixed_case)
                     )
                 }
+            };
+
+            format!("{inferred_comment}{relation_xml}")
+        }
+
+        fn generate_cascade_xml(settings: &RelationSettings, to_many: bool) -> String {
+            let mut cascade: Vec<&str> = settings.cascade().iter().map(|c| c.to_xml()).collect();
+
+            // Hibernate only deletes the orphans of the collections
+            if *settings.orphan_removal() && to_many {
+                cascade.push("delete-orphan");
+            }
+
+            if cascade.is_empty() {
+                "".to_string()
+            } else {
+                format!(r#" cascade="{}""#, cascade.join(","))
             }
         }
     }
 
-    fn generate_table_java(&self, table: &Table) -> String {
+    fn generate_table_java(&self, table: &Table, relations: &RelationEnds) -> String {
         let package = &self.package;
-        let class_name = naming::to_upper_camel_case(table.name());
+        let class_name = self.options.naming().class_name(table.name());
 
         let table_id = table.ids();
+        let immutable = hibernate::is_immutable(table, self.sniff_results, &self.options);
 
         // Generating basic fields based on columns
 
-        let mut fields: Vec<Field> = if table_id.len() == 1 {
+        let mut fields: Vec<Field> = if table_id.len() == 1 || immutable {
             table
                 .columns()
                 .iter()
                 .filter(|c| table_id.contains(c) || !table.is_col_fk(c.name()))
-                .map(hibernate::generate_field)
+                .map(|c| hibernate::generate_field(c, &self.options))
                 .collect()
         } else {
             let mut fields: Vec<Field> = table
                 .columns()
                 .iter()
                 .filter(|c| !(table_id.contains(c) || table.is_col_fk(c.name())))
-                .map(hibernate::generate_field)
+                .map(|c| hibernate::generate_field(c, &self.options))
                 .collect();
             
             fields.push(Field::new(
@@ -566,70 +814,42 @@ impl<'a> XMLGenerator<'a> {
             fields
         };
 
-        let mut used_names: HashMap<&String, i32> = HashMap::new();
-
-        gen_rel_fields(
-            table.references().iter().collect::<Vec<&Relation>>().iter(),
-            true,
-            &mut fields,
-            &mut used_names,
-        );
-        gen_rel_fields(
-            self.sniff_results
-                .database()
-                .table_referenced_by(table.name())
-                .iter(),
-            false,
-            &mut fields,
-            &mut used_names,
-        );
+        relations
+            .of_table(table)
+            .iter()
+            .for_each(|p| {
+                let field_type =
+                    Type::new(self.options.naming().class_name(p.ref_table_name()), "".to_string());
+                let settings = self.options.relations().settings(table.name(), &p.name);
+                let collection = Collection::of(p, settings, &self.database);
+
+                fields.push(hibernate::gen_rel_field(
+                    p.name.clone(),
+                    field_type,
+                    collection.as_ref(),
+                    self.options.type_mapping(),
+                ));
+            });
 
         let methods = fields.iter().flat_map(|f| f.getters_setters()).collect();
 
-        let java_class = Class::new(class_name.clone(), package.clone(), fields, methods);
-
-        return java_class.into();
-
-        // TODO: Ugly function again
-        fn gen_rel_fields<'a>(
-            relations: Iter<&'a Relation>,
-            rel_owner: bool,
-            fields: &mut Vec<Field>,
-            used_name: &mut HashMap<&'a String, i32>,
-        ) {
-            relations.for_each(|r| {
-                let ref_table_name = if rel_owner {
-                    r.to()[0].table()
-                } else {
-                    r.from()[0].table()
-                };
-
-                let field_name = if let Some(count) = used_name.get_mut(ref_table_name) {
-                    *count += 1;
-                    format!("{}{}", naming::to_lower_camel_case(ref_table_name), count)
-                } else {
-                    used_name.insert(ref_table_name, 1);
-                    naming::to_lower_camel_case(ref_table_name).to_string()
-                };
+        let mut java_class = Class::new(class_name.clone(), package.clone(), fields, methods);
 
-                let field_type =
-                    Type::new(naming::to_upper_camel_case(ref_table_name), "".to_string());
-
-                let field = hibernate::gen_rel_field(r.r#type(), rel_owner, field_name, field_type);
-
-                fields.push(field);
-            });
+        if let Some(doc) = hibernate::class_doc(table) {
+            java_class.set_doc(doc);
         }
+
+        java_class.into()
     }
 
     fn generate_composite_id(&self, table: &Table) -> String {
         let package = &self.package;
-        let class_name = naming::to_upper_camel_case(table.name());
+        let class_name = self.options.naming().class_name(table.name());
 
         let fields: Vec<Field> = table
             .ids()
             .iter()
-            .map(|c| hibernate::generate_field(c))
+            .map(|c| hibernate::generate_field(c, &self.options))
             .collect();
 
         let methods = fields.iter().flat_map(|f| f.getters_setters()).collect();
@@ -653,10 +873,8 @@ impl<'a> XMLGenerator<'a> {
     }
 }
 
-fn escape_xml_special_chars(text: &str) -> String {
-    text.replace("&", "&amp;")
-        .replace("<", "&lt;")
-        .replace(">", "&gt;")
-        .replace("\"", "&quot;")
-        .replace("'", "&apos;")
+/// Table or column name, quoted with backticks when it is reserved or not a plain identifier, or
+/// on demand when it is mixed case
+fn sql_name(name: &str, quote_mixed_case: bool) -> String {
+    hibernate::escape_xml_special_chars(&naming::quote_sql(name, "`", "`", quote_mixed_case))
 }
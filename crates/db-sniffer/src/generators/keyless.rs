@@ -0,0 +1,209 @@
+use crate::db_objects::{Database, GenerationType, KeyType, Table};
+use crate::diagnostics::{Diagnostic, DiagnosticKind};
+use std::str::FromStr;
+
+/// How the tables without a primary key are mapped, skipped by default
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum KeylessStrategy {
+    /// The tables are not mapped, neither the relations to them
+    #[default]
+    Skip,
+    /// The `NOT NULL` unique columns are the id, the tables without any are skipped
+    UniqueKey,
+    /// All the columns are the id
+    AllColumns,
+    /// All the columns are the id of an immutable entity, mapped with an `@IdClass`
+    IdClass,
+}
+
+impl FromStr for KeylessStrategy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "skip" => Ok(KeylessStrategy::Skip),
+            "unique" => Ok(KeylessStrategy::UniqueKey),
+            "all-columns" => Ok(KeylessStrategy::AllColumns),
+            "id-class" => Ok(KeylessStrategy::IdClass),
+            _ => Err(()),
+        }
+    }
+}
+
+impl KeylessStrategy {
+    /// The table, as sniffed, is mapped as an immutable entity
+    pub(crate) fn is_immutable(&self, table: &Table) -> bool {
+        *self == KeylessStrategy::IdClass && table.ids().is_empty()
+    }
+}
+
+/// Copy of the database where the keyless tables have the id chosen by the strategy. The tables
+/// left without an id are removed along with the relations to them. Both are reported in the
+/// diagnostics.
+pub(crate) fn apply(
+    database: &Database,
+    strategy: KeylessStrategy,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Database {
+    let mut database = database.clone();
+    let mut skipped = Vec::new();
+
+    for table in database.tables_mut() {
+        if !table.ids().is_empty() {
+            continue;
+        }
+
+        let ids = table
+            .columns()
+            .iter()
+            .filter(|c| match strategy {
+                KeylessStrategy::Skip => false,
+                KeylessStrategy::UniqueKey => {
+                    matches!(c.key(), KeyType::Unique) && c.not_nullable()
+                }
+                KeylessStrategy::AllColumns | KeylessStrategy::IdClass => true,
+            })
+            .map(|c| c.name().to_string())
+            .collect::<Vec<String>>();
+
+        if ids.is_empty() {
+            let message = match strategy {
+                KeylessStrategy::Skip => format!(
+                    "Table {} skipped, it has no primary key and the keyless strategy is skip",
+                    table.name()
+                ),
+                _ => format!(
+                    "Table {} skipped, it has neither a primary key nor NOT NULL unique columns",
+                    table.name()
+                ),
+            };
+
+            diagnostics.push(Diagnostic::new(
+                DiagnosticKind::SkippedObject,
+                Some(table.name()),
+                None,
+                &message,
+            ));
+            skipped.push(table.name().to_string());
+            continue;
+        }
+
+        diagnostics.push(Diagnostic::new(
+            DiagnosticKind::MissingPrimaryKey,
+            Some(table.name()),
+            None,
+            &format!(
+                "Table {} has no primary key, mapped with {} as the id",
+                table.name(),
+                ids.join(", ")
+            ),
+        ));
+
+        for column in table.columns_mut() {
+            if ids.iter().any(|id| id == column.name()) {
+                column.set_key(KeyType::Primary(GenerationType::None));
+            }
+        }
+    }
+
+    database
+        .tables_mut()
+        .retain(|t| !skipped.contains(t.name()));
+
+    for table in database.tables_mut() {
+        table
+            .references_mut()
+            .retain(|r| !r.to().iter().any(|c| skipped.iter().any(|s| s == c.table())));
+    }
+
+    database
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_objects::{Column, ColumnId, ColumnType, Relation, RelationType};
+
+    fn database() -> Database {
+        let column = |table: &str, name: &str, nullable: bool, key: KeyType| {
+            Column::new(ColumnId::new(table, name), ColumnType::Integer(0), nullable, key)
+        };
+
+        let mut person = Table::new("person");
+        person.add_column(column("person", "id", false, KeyType::Primary(GenerationType::None)));
+
+        let mut audit_log = Table::new("audit_log");
+        audit_log.add_column(column("audit_log", "code", false, KeyType::Unique));
+        audit_log.add_column(column("audit_log", "email", true, KeyType::Unique));
+        audit_log.add_column(column("audit_log", "person_id", false, KeyType::None));
+        audit_log.add_reference_to(
+            Relation::new(
+                vec![ColumnId::new("audit_log", "person_id")],
+                vec![ColumnId::new("person", "id")],
+                RelationType::ManyToOne,
+            )
+            .unwrap(),
+        );
+
+        let mut event = Table::new("event");
+        event.add_column(column("event", "person_id", false, KeyType::None));
+        event.add_reference_to(
+            Relation::new(
+                vec![ColumnId::new("event", "person_id")],
+                vec![ColumnId::new("person", "id")],
+                RelationType::ManyToOne,
+            )
+            .unwrap(),
+        );
+
+        let mut database = Database::new("test_db");
+        database.add_table(person);
+        database.add_table(audit_log);
+        database.add_table(event);
+        database
+    }
+
+    fn ids(database: &Database, table: &str) -> Option<Vec<String>> {
+        database
+            .table(table)
+            .map(|t| t.ids().iter().map(|c| c.name().to_string()).collect())
+    }
+
+    #[test]
+    fn test_apply() {
+        let sniffed = database();
+
+        let mut diagnostics = Vec::new();
+
+        let skip = apply(&sniffed, KeylessStrategy::Skip, &mut diagnostics);
+        assert_eq!(ids(&skip, "person"), Some(vec!["id".to_string()]));
+        assert_eq!(ids(&skip, "audit_log"), None);
+        assert!(skip.table_referenced_by("person").is_empty());
+
+        let unique = apply(&sniffed, KeylessStrategy::UniqueKey, &mut diagnostics);
+        assert_eq!(ids(&unique, "audit_log"), Some(vec!["code".to_string()]));
+        assert_eq!(ids(&unique, "event"), None);
+        assert_eq!(unique.table_referenced_by("person").len(), 1);
+
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|d| (*d.kind(), d.table().clone().unwrap_or_default()))
+                .collect::<Vec<(DiagnosticKind, String)>>(),
+            vec![
+                (DiagnosticKind::SkippedObject, "audit_log".to_string()),
+                (DiagnosticKind::SkippedObject, "event".to_string()),
+                (DiagnosticKind::MissingPrimaryKey, "audit_log".to_string()),
+                (DiagnosticKind::SkippedObject, "event".to_string()),
+            ]
+        );
+
+        let all_columns = apply(&sniffed, KeylessStrategy::AllColumns, &mut Vec::new());
+        assert_eq!(ids(&all_columns, "audit_log").map(|ids| ids.len()), Some(3));
+        assert_eq!(ids(&all_columns, "event"), Some(vec!["person_id".to_string()]));
+
+        assert!(KeylessStrategy::IdClass.is_immutable(sniffed.table("event").unwrap()));
+        assert!(!KeylessStrategy::IdClass.is_immutable(sniffed.table("person").unwrap()));
+        assert!(!KeylessStrategy::AllColumns.is_immutable(sniffed.table("event").unwrap()));
+    }
+}
@@ -1,9 +1,132 @@
+mod audit;
+mod ddl;
 mod hibernate;
 mod java;
+mod keyless;
+mod relation_mapping;
+mod types;
 
+use crate::diagnostics::{self, Diagnostic};
+use crate::naming::{DefaultNaming, Language, NamingStrategy, SanitizedNaming};
+use crate::sniffers::SniffResults;
+use getset::{Getters, MutGetters, Setters};
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub use audit::AuditConvention;
+pub use audit::AuditMapping;
+pub use audit::AuditRole;
+pub use ddl::DDLGenerator;
 pub use hibernate::XMLGenerator;
 pub use hibernate::JPAGenerator;
+pub use keyless::KeylessStrategy;
+pub use relation_mapping::CascadeType;
+pub use relation_mapping::CollectionType;
+pub use relation_mapping::FetchMode;
+pub use relation_mapping::RelationMapping;
+pub use relation_mapping::RelationSettings;
+pub use types::Conversion;
+pub use types::TypeMapping;
+pub use types::TypeOverride;
 
 pub trait Generator {
     fn generate(&self) -> Result<(), crate::Error>;
-}
\ No newline at end of file
+}
+
+/// Issues of the code generated with the options from the sniffed database, to be reported along
+/// with the diagnostics of the sniffing
+pub fn diagnose(sniff_results: &SniffResults, options: &GenerationOptions) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    // The keyless tables are generated as the strategy maps them
+    let database = keyless::apply(sniff_results.database(), *options.keyless(), &mut diagnostics);
+
+    diagnostics.extend(diagnostics::name_collisions(&database, options.naming().as_ref()));
+    diagnostics.extend(hibernate::diagnose_converters(&database, options.type_mapping()));
+
+    diagnostics
+}
+
+/// Files generated from the sniffed database
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GenerationMode {
+    Ddl,
+    HibernateXml,
+    HibernateJpa,
+}
+
+impl FromStr for GenerationMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "0" | "ddl" => Ok(GenerationMode::Ddl),
+            "1" | "hibernate-xml" | "xml" => Ok(GenerationMode::HibernateXml),
+            "2" | "hibernate-jpa" | "jpa" => Ok(GenerationMode::HibernateJpa),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Options tuning the code generated from the sniffed database
+#[derive(Getters, Setters, MutGetters, Clone, Debug)]
+pub struct GenerationOptions {
+    /// Recognition and mapping of the audit columns
+    #[getset(get = "pub", set = "pub")]
+    audit: AuditConvention,
+    /// Java and Hibernate types of the columns
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    type_mapping: TypeMapping,
+    /// Primitive types for the `NOT NULL` columns instead of their wrappers
+    #[getset(get = "pub", set = "pub")]
+    primitives: bool,
+    /// Mapping of the tables without a primary key
+    #[getset(get = "pub", set = "pub")]
+    keyless: KeylessStrategy,
+    /// The referenced tables also hold the relations, mapped by the properties of the owners
+    #[getset(get = "pub", set = "pub")]
+    bidirectional: bool,
+    /// Loading, cascading and collections of the relations
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    relations: RelationMapping,
+    /// Java names of the tables, columns and relations
+    #[getset(get = "pub")]
+    naming: Arc<dyn NamingStrategy>,
+    /// Package of the generated classes, instead of the one of the output directory
+    #[getset(get = "pub", set = "pub")]
+    package: Option<String>,
+    /// Written to the generated configuration instead of the password, e.g. `${env.DB_PASSWORD}`
+    #[getset(get = "pub", set = "pub")]
+    password_placeholder: Option<String>,
+    /// The table and column names with upper and lower case letters are also quoted in SQL
+    #[getset(get = "pub", set = "pub")]
+    quote_mixed_case: bool,
+}
+
+impl GenerationOptions {
+    /// The names of the strategy are sanitized, so they are valid Java identifiers
+    pub fn set_naming(&mut self, naming: Arc<dyn NamingStrategy>) -> &mut Self {
+        self.naming = Arc::new(SanitizedNaming::new(naming, Language::Java));
+        self
+    }
+}
+
+impl Default for GenerationOptions {
+    fn default() -> Self {
+        GenerationOptions {
+            audit: AuditConvention::default(),
+            type_mapping: TypeMapping::default(),
+            primitives: false,
+            keyless: KeylessStrategy::default(),
+            bidirectional: true,
+            relations: RelationMapping::default(),
+            naming: Arc::new(SanitizedNaming::new(
+                Arc::new(DefaultNaming::default()),
+                Language::Java,
+            )),
+            package: None,
+            password_placeholder: None,
+            quote_mixed_case: false,
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use getset::{Getters, MutGetters, Setters};
+use glob::{MatchOptions, Pattern};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// How Hibernate loads the entities of a relation
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum FetchMode {
+    /// A query per relation when it is accessed
+    #[default]
+    Select,
+    /// Joined to the query of the entity holding the relation
+    Join,
+    /// A query loading the collections of all the entities of the first query, collections only
+    Subselect,
+}
+
+impl FromStr for FetchMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "select" => Ok(FetchMode::Select),
+            "join" => Ok(FetchMode::Join),
+            "subselect" => Ok(FetchMode::Subselect),
+            _ => Err(()),
+        }
+    }
+}
+
+impl FetchMode {
+    /// Value of the `fetch` attribute of HBM.XML. The single entities can not be subselected.
+    pub(crate) fn to_xml(self, to_many: bool) -> &'static str {
+        match self {
+            FetchMode::Select => "select",
+            FetchMode::Join => "join",
+            FetchMode::Subselect if to_many => "subselect",
+            FetchMode::Subselect => "select",
+        }
+    }
+
+    /// Constant of `org.hibernate.annotations.FetchMode`
+    pub(crate) fn to_jpa(self) -> &'static str {
+        match self {
+            FetchMode::Select => "FetchMode.SELECT",
+            FetchMode::Join => "FetchMode.JOIN",
+            FetchMode::Subselect => "FetchMode.SUBSELECT",
+        }
+    }
+}
+
+/// Operations on an entity propagated to the entities of its relation
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CascadeType {
+    All,
+    Persist,
+    Merge,
+    Remove,
+    Refresh,
+    Detach,
+}
+
+impl FromStr for CascadeType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(CascadeType::All),
+            "persist" => Ok(CascadeType::Persist),
+            "merge" => Ok(CascadeType::Merge),
+            "remove" => Ok(CascadeType::Remove),
+            "refresh" => Ok(CascadeType::Refresh),
+            "detach" => Ok(CascadeType::Detach),
+            _ => Err(()),
+        }
+    }
+}
+
+impl CascadeType {
+    /// Value of the `cascade` attribute of HBM.XML
+    pub(crate) fn to_xml(self) -> &'static str {
+        match self {
+            CascadeType::All => "all",
+            CascadeType::Persist => "persist",
+            CascadeType::Merge => "merge",
+            CascadeType::Remove => "delete",
+            CascadeType::Refresh => "refresh",
+            CascadeType::Detach => "evict",
+        }
+    }
+
+    /// Constant of `jakarta.persistence.CascadeType`
+    pub(crate) fn to_jpa(self) -> &'static str {
+        match self {
+            CascadeType::All => "CascadeType.ALL",
+            CascadeType::Persist => "CascadeType.PERSIST",
+            CascadeType::Merge => "CascadeType.MERGE",
+            CascadeType::Remove => "CascadeType.REMOVE",
+            CascadeType::Refresh => "CascadeType.REFRESH",
+            CascadeType::Detach => "CascadeType.DETACH",
+        }
+    }
+}
+
+/// Java collection holding the entities of a to-many relation
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum CollectionType {
+    #[default]
+    Set,
+    /// Ordered by the order column, if any
+    List,
+    /// Keyed by a column of the entities, their id by default
+    Map,
+}
+
+impl FromStr for CollectionType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "set" => Ok(CollectionType::Set),
+            "list" => Ok(CollectionType::List),
+            "map" => Ok(CollectionType::Map),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Loading and cascading of a relation and, for the to-many ones, its collection
+#[derive(Getters, Setters, Clone, PartialEq, Debug)]
+pub struct RelationSettings {
+    /// The entities are loaded when the relation is accessed
+    #[getset(get = "pub", set = "pub")]
+    lazy: bool,
+    #[getset(get = "pub", set = "pub")]
+    fetch: FetchMode,
+    #[getset(get = "pub", set = "pub")]
+    cascade: Vec<CascadeType>,
+    /// The entities removed from the relation are deleted
+    #[getset(get = "pub", set = "pub")]
+    orphan_removal: bool,
+    #[getset(get = "pub", set = "pub")]
+    collection: CollectionType,
+    /// Column of the entities keeping their position in a `List`
+    #[getset(get = "pub", set = "pub")]
+    order_column: Option<String>,
+    /// Column of the entities keying a `Map`, their id when it is not set
+    #[getset(get = "pub", set = "pub")]
+    map_key: Option<String>,
+}
+
+impl Default for RelationSettings {
+    fn default() -> Self {
+        RelationSettings {
+            lazy: true,
+            fetch: FetchMode::default(),
+            cascade: Vec::new(),
+            orphan_removal: false,
+            collection: CollectionType::default(),
+            order_column: None,
+            map_key: None,
+        }
+    }
+}
+
+/// Settings of the relations, the same for all of them except the overridden ones
+#[derive(Getters, Setters, MutGetters, Clone, Debug, Default)]
+pub struct RelationMapping {
+    #[getset(get = "pub", set = "pub", get_mut = "pub")]
+    defaults: RelationSettings,
+    /// Settings of the relation properties matching a `table.property` pattern
+    overrides: Vec<(Pattern, RelationSettings)>,
+}
+
+impl RelationMapping {
+    /// Reads the settings of a TOML file like
+    ///
+    /// ```toml
+    /// [defaults]
+    /// cascade = ["persist", "merge"]
+    ///
+    /// [relations]
+    /// "purchase_order.lines" = { cascade = ["all"], orphan-removal = true, collection = "list", order-column = "line_no" }
+    /// "customer.*" = { lazy = false, fetch = "join" }
+    /// ```
+    ///
+    /// The relations are the properties of the tables holding them, and their settings
+    /// override the defaults.
+    pub fn load(path: &Path) -> Result<Self, crate::Error> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            crate::Error::ConfigError(format!("{}: {e}", path.to_str().unwrap_or_default()))
+        })?;
+
+        content.parse()
+    }
+
+    /// Overrides the settings of the relation properties matching a `table.property` pattern
+    pub fn add_override(
+        &mut self,
+        pattern: &str,
+        settings: RelationSettings,
+    ) -> Result<(), crate::Error> {
+        let pattern = Pattern::new(pattern)
+            .map_err(|e| crate::Error::ConfigError(format!("Invalid pattern {pattern}: {e}")))?;
+
+        self.overrides.push((pattern, settings));
+        Ok(())
+    }
+
+    /// Settings of the property of a table. The exact `table.property` overrides go before the
+    /// patterns, and these before the defaults.
+    pub fn settings(&self, table_name: &str, property_name: &str) -> &RelationSettings {
+        let name = format!("{table_name}.{property_name}");
+        let options = MatchOptions {
+            case_sensitive: false,
+            ..MatchOptions::default()
+        };
+
+        self.overrides
+            .iter()
+            .find(|(p, _)| p.as_str().eq_ignore_ascii_case(&name))
+            .or_else(|| {
+                self.overrides
+                    .iter()
+                    .find(|(p, _)| p.matches_with(&name, options))
+            })
+            .map(|(_, s)| s)
+            .unwrap_or(&self.defaults)
+    }
+}
+
+impl FromStr for RelationMapping {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct SettingsFile {
+            #[serde(default)]
+            defaults: Option<toml::Table>,
+            #[serde(default)]
+            relations: toml::Table,
+        }
+
+        let file: SettingsFile =
+            toml::from_str(s).map_err(|e| crate::Error::ConfigError(e.to_string()))?;
+
+        RelationMapping::from_toml(file.defaults, file.relations)
+    }
+}
+
+impl RelationMapping {
+    /// Mapping of the `[defaults]` and `[relations]` tables of a TOML file
+    pub(crate) fn from_toml(
+        defaults: Option<toml::Table>,
+        relations: toml::Table,
+    ) -> Result<Self, crate::Error> {
+        let mut mapping = RelationMapping::default();
+
+        if let Some(defaults) = defaults {
+            mapping.defaults = SettingsEntry::parse(defaults.into())?.apply(&mapping.defaults)?;
+        }
+
+        for (pattern, value) in relations {
+            let settings = SettingsEntry::parse(value)?.apply(&mapping.defaults)?;
+            mapping.add_override(&pattern, settings)?;
+        }
+
+        Ok(mapping)
+    }
+}
+
+/// Settings of a TOML entry, the missing ones taken from the defaults
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct SettingsEntry {
+    lazy: Option<bool>,
+    fetch: Option<String>,
+    cascade: Option<Vec<String>>,
+    orphan_removal: Option<bool>,
+    collection: Option<String>,
+    order_column: Option<String>,
+    map_key: Option<String>,
+}
+
+impl SettingsEntry {
+    fn parse(value: toml::Value) -> Result<Self, crate::Error> {
+        value
+            .try_into()
+            .map_err(|e: toml::de::Error| crate::Error::ConfigError(e.to_string()))
+    }
+
+    fn apply(self, defaults: &RelationSettings) -> Result<RelationSettings, crate::Error> {
+        let invalid =
+            |what: &str, value: &str| crate::Error::ConfigError(format!("Unknown {what}: {value}"));
+
+        let mut settings = defaults.clone();
+
+        if let Some(lazy) = self.lazy {
+            settings.lazy = lazy;
+        }
+
+        if let Some(fetch) = self.fetch {
+            settings.fetch = FetchMode::from_str(&fetch).map_err(|_| invalid("fetch mode", &fetch))?;
+        }
+
+        if let Some(cascade) = self.cascade {
+            settings.cascade = cascade
+                .iter()
+                .map(|c| CascadeType::from_str(c).map_err(|_| invalid("cascade type", c)))
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Some(orphan_removal) = self.orphan_removal {
+            settings.orphan_removal = orphan_removal;
+        }
+
+        if let Some(collection) = self.collection {
+            settings.collection = CollectionType::from_str(&collection)
+                .map_err(|_| invalid("collection type", &collection))?;
+        }
+
+        if self.order_column.is_some() {
+            settings.order_column = self.order_column;
+        }
+
+        if self.map_key.is_some() {
+            settings.map_key = self.map_key;
+        }
+
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relation_mapping() {
+        let mapping: RelationMapping = r#"
+            [defaults]
+            cascade = ["persist", "merge"]
+
+            [relations]
+            "purchase_order.lines" = { cascade = ["all"], orphan-removal = true, collection = "list", order-column = "line_no" }
+            "customer.*" = { lazy = false, fetch = "join" }
+        "#
+        .parse()
+        .unwrap();
+
+        let lines = mapping.settings("PURCHASE_ORDER", "lines");
+        assert_eq!(lines.cascade(), &vec![CascadeType::All]);
+        assert!(*lines.orphan_removal());
+        assert_eq!(*lines.collection(), CollectionType::List);
+        assert_eq!(lines.order_column().as_deref(), Some("line_no"));
+
+        let orders = mapping.settings("customer", "orders");
+        assert!(!*orders.lazy());
+        assert_eq!(*orders.fetch(), FetchMode::Join);
+        assert_eq!(orders.cascade(), &vec![CascadeType::Persist, CascadeType::Merge]);
+
+        let other = mapping.settings("person", "department");
+        assert_eq!(other, mapping.defaults());
+        assert!(*other.lazy());
+
+        assert!("[relations]\n\"a.b\" = { cascade = [\"delete\"] }"
+            .parse::<RelationMapping>()
+            .is_err());
+        assert!("[defaults]\neager = true".parse::<RelationMapping>().is_err());
+    }
+}
@@ -0,0 +1,493 @@
+use crate::db_objects::{Column, ColumnType, TypeProfile};
+use getset::{Getters, MutGetters, Setters};
+use glob::{MatchOptions, Pattern};
+use serde::Deserialize;
+use std::fs;
+use std::mem;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Java and Hibernate types used for the columns
+#[derive(Getters, Setters, MutGetters, Clone, Debug, Default)]
+pub struct TypeMapping {
+    #[getset(get = "pub", set = "pub")]
+    profile: TypeProfile,
+    #[getset(get = "pub", get_mut = "pub")]
+    overrides: Vec<TypeOverride>,
+    /// Package of the generated converters
+    #[getset(get = "pub", set = "pub")]
+    converters_package: String,
+}
+
+impl TypeMapping {
+    /// Reads the overrides of a TOML file like
+    ///
+    /// ```toml
+    /// [types]
+    /// "char(1)" = { java = "Boolean", conversion = "yes_no" }
+    ///
+    /// [columns]
+    /// "*.is_*" = { java = "Boolean" }
+    /// "invoice.total" = { java = "com.example.Money", converter = "com.example.MoneyConverter" }
+    /// ```
+    pub fn load(path: &Path) -> Result<Self, crate::Error> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            crate::Error::ConfigError(format!("{}: {e}", path.to_str().unwrap_or_default()))
+        })?;
+
+        content.parse()
+    }
+
+    pub fn add_override(&mut self, type_override: TypeOverride) {
+        self.overrides.push(type_override);
+    }
+
+    /// Adds the overrides of another mapping, which replace the ones of the same column type or
+    /// column pattern and go first
+    pub fn merge(&mut self, other: TypeMapping) {
+        self.overrides
+            .retain(|o| !other.overrides.iter().any(|other| other.target == o.target));
+        self.overrides.splice(0..0, other.overrides);
+    }
+
+    /// Override of the column. The exact `table.column` overrides go before the patterns, and
+    /// these before the overrides of the column type.
+    pub fn column_override(&self, column: &Column) -> Option<&TypeOverride> {
+        let name = format!("{}.{}", column.table(), column.name());
+        let options = MatchOptions {
+            case_sensitive: false,
+            ..MatchOptions::default()
+        };
+
+        let columns = self.overrides.iter().filter_map(|o| match &o.target {
+            OverrideTarget::Column(pattern) => Some((o, pattern)),
+            OverrideTarget::Type(_, _) => None,
+        });
+
+        columns
+            .clone()
+            .find(|(_, p)| p.as_str().eq_ignore_ascii_case(&name))
+            .or_else(|| columns.clone().find(|(_, p)| p.matches_with(&name, options)))
+            .map(|(o, _)| o)
+            .or_else(|| self.type_override(column.r#type()))
+    }
+
+    /// Java type of the columns of a type, overridden or the one of the profile
+    pub fn java_type(&self, column_type: &ColumnType) -> dotjava::Type {
+        self.type_override(column_type)
+            .and_then(|o| o.java_type())
+            .unwrap_or_else(|| column_type.to_java(self.profile))
+    }
+
+    /// Hibernate type of the columns of a type, overridden or the one of the profile
+    pub fn hibernate_type(&self, column_type: &ColumnType) -> String {
+        let column_java = column_type.to_java(self.profile);
+
+        self.type_override(column_type)
+            .and_then(|o| o.hibernate_type(&self.converters_package, &column_java))
+            .unwrap_or_else(|| column_type.to_hibernate(self.profile))
+    }
+
+    /// Java type of the column, taking into account the overrides of the column
+    pub fn column_java_type(&self, column: &Column) -> dotjava::Type {
+        self.column_override(column)
+            .and_then(|o| o.java_type())
+            .unwrap_or_else(|| self.java_type(column.r#type()))
+    }
+
+    /// Hibernate type of the column, taking into account the overrides of the column
+    pub fn column_hibernate_type(&self, column: &Column) -> String {
+        let column_java = column.r#type().to_java(self.profile);
+
+        self.column_override(column)
+            .and_then(|o| o.hibernate_type(&self.converters_package, &column_java))
+            .unwrap_or_else(|| self.hibernate_type(column.r#type()))
+    }
+
+    /// Fully qualified name of the converter of the column, if it has one
+    pub fn column_converter_class(&self, column: &Column) -> Option<String> {
+        let column_java = column.r#type().to_java(self.profile);
+
+        self.column_override(column)
+            .and_then(|o| o.converter_class(&self.converters_package, &column_java))
+    }
+
+    pub fn type_override(&self, column_type: &ColumnType) -> Option<&TypeOverride> {
+        self.overrides.iter().find(|o| match &o.target {
+            OverrideTarget::Type(r#type, true) => r#type == column_type,
+            OverrideTarget::Type(r#type, false) => {
+                mem::discriminant(r#type) == mem::discriminant(column_type)
+            }
+            OverrideTarget::Column(_) => false,
+        })
+    }
+}
+
+impl FromStr for TypeMapping {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        #[derive(Deserialize)]
+        struct OverridesFile {
+            #[serde(default)]
+            types: toml::Table,
+            #[serde(default)]
+            columns: toml::Table,
+        }
+
+        let file: OverridesFile =
+            toml::from_str(s).map_err(|e| crate::Error::ConfigError(e.to_string()))?;
+
+        TypeMapping::from_toml(file.types, file.columns)
+    }
+}
+
+impl TypeMapping {
+    /// Mapping of the overrides of the `[types]` and `[columns]` tables of a TOML file
+    pub(crate) fn from_toml(types: toml::Table, columns: toml::Table) -> Result<Self, crate::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Entry {
+            java: Option<String>,
+            hibernate: Option<String>,
+            converter: Option<String>,
+            conversion: Option<String>,
+        }
+
+        let mut mapping = TypeMapping::default();
+
+        let entries = types
+            .into_iter()
+            .map(|(key, value)| (TypeOverride::for_type(&key), key, value))
+            .chain(
+                columns
+                    .into_iter()
+                    .map(|(key, value)| (TypeOverride::for_column(&key), key, value)),
+            );
+
+        for (type_override, key, value) in entries {
+            let entry: Entry = value
+                .try_into()
+                .map_err(|e: toml::de::Error| crate::Error::ConfigError(e.to_string()))?;
+
+            let mut type_override = type_override?;
+            type_override.java = entry.java;
+            type_override.hibernate = entry.hibernate;
+            type_override.converter = entry.converter;
+            type_override.conversion = entry
+                .conversion
+                .map(|c| {
+                    Conversion::from_str(&c).map_err(|_| {
+                        crate::Error::ConfigError(format!("Unknown conversion: {c}"))
+                    })
+                })
+                .transpose()?;
+
+            // Hibernate only maps the classes of its basic types without a user type or converter
+            if let Some(java) = type_override.java()
+                && type_override.hibernate.is_none()
+                && type_override.converter.is_none()
+                && type_override.conversion.is_none()
+                && basic_hibernate_type(java).is_none()
+            {
+                return Err(crate::Error::ConfigError(format!(
+                    "{key}: {java} is not a basic type, a hibernate type or a converter is required"
+                )));
+            }
+
+            mapping.add_override(type_override);
+        }
+
+        Ok(mapping)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum OverrideTarget {
+    /// Column type, and whether its size has to match too
+    Type(ColumnType, bool),
+    /// `table.column` glob pattern
+    Column(Pattern),
+}
+
+/// Conversions implemented by the generated `AttributeConverter`s
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Conversion {
+    /// `Boolean` saved as `Y`/`N`
+    YesNo,
+    /// `List<String>` saved as comma separated values
+    Csv,
+    /// Any type saved as JSON with Jackson
+    Json,
+}
+
+impl FromStr for Conversion {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "yes_no" => Ok(Conversion::YesNo),
+            "csv" => Ok(Conversion::Csv),
+            "json" => Ok(Conversion::Json),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Types replacing the default ones of a column type or of some columns
+#[derive(Getters, Setters, Clone, Debug)]
+pub struct TypeOverride {
+    target: OverrideTarget,
+    /// Java type, fully qualified if it is not in `java.lang`
+    #[getset(get = "pub", set = "pub")]
+    java: Option<String>,
+    #[getset(get = "pub", set = "pub")]
+    hibernate: Option<String>,
+    /// Fully qualified name of the `AttributeConverter` of the column. With a conversion, the
+    /// name of the generated converter.
+    #[getset(get = "pub", set = "pub")]
+    converter: Option<String>,
+    /// Conversion of the generated converter
+    #[getset(get = "pub", set = "pub")]
+    conversion: Option<Conversion>,
+}
+
+impl TypeOverride {
+    /// Override of a column type, e.g. `char(1)` or any `decimal`
+    pub fn for_type(column_type: &str) -> Result<Self, crate::Error> {
+        let column_type_lower = column_type.to_lowercase();
+
+        let r#type = ColumnType::from_str(&column_type_lower).map_err(|_| {
+            crate::Error::ConfigError(format!("Unknown column type: {column_type}"))
+        })?;
+
+        Ok(Self::new(OverrideTarget::Type(
+            r#type,
+            column_type_lower.contains('('),
+        )))
+    }
+
+    /// Override of the columns matching a `table.column` pattern, e.g. `*.is_*`. A pattern
+    /// without table applies to every table.
+    pub fn for_column(pattern: &str) -> Result<Self, crate::Error> {
+        let pattern = if pattern.contains('.') {
+            pattern.to_string()
+        } else {
+            format!("*.{pattern}")
+        };
+
+        let pattern = Pattern::new(&pattern)
+            .map_err(|e| crate::Error::ConfigError(format!("Invalid pattern {pattern}: {e}")))?;
+
+        Ok(Self::new(OverrideTarget::Column(pattern)))
+    }
+
+    fn new(target: OverrideTarget) -> Self {
+        TypeOverride {
+            target,
+            java: None,
+            hibernate: None,
+            converter: None,
+            conversion: None,
+        }
+    }
+
+    /// Java type of the override, given or implied by the conversion
+    pub fn java_type(&self) -> Option<dotjava::Type> {
+        let java = self.java.as_deref().or(match self.conversion {
+            Some(Conversion::YesNo) => Some("Boolean"),
+            Some(Conversion::Csv) => Some("java.util.List<String>"),
+            Some(Conversion::Json) | None => None,
+        });
+
+        java.map(java_type)
+    }
+
+    /// Simple name of the generated converter of a column of `column_type`, the Java type of the
+    /// column in the database. The generated names include the column type when it is not a
+    /// `String`, e.g. `YesNoCharacterConverter`.
+    pub fn converter_name(&self, column_type: &dotjava::Type) -> Option<String> {
+        let conversion = self.conversion?;
+
+        if let Some(converter) = &self.converter {
+            return Some(converter.rsplit('.').next().unwrap_or(converter).to_string());
+        }
+
+        let column = String::from(column_type.clone());
+        let column = if column == "String" { "".to_string() } else { class_name_part(&column) };
+
+        let name = match conversion {
+            Conversion::YesNo => format!("YesNo{column}Converter"),
+            Conversion::Csv => format!("Csv{column}Converter"),
+            Conversion::Json => {
+                let java = self.java_type().map(String::from).unwrap_or_default();
+
+                format!("{}Json{column}Converter", class_name_part(&java))
+            }
+        };
+
+        Some(name)
+    }
+
+    /// Fully qualified name of the converter, the generated ones being in `converters_package`
+    pub fn converter_class(
+        &self,
+        converters_package: &str,
+        column_type: &dotjava::Type,
+    ) -> Option<String> {
+        match self.converter_name(column_type) {
+            Some(name) if converters_package.is_empty() => Some(name),
+            Some(name) => Some(format!("{converters_package}.{name}")),
+            None => self.converter.clone(),
+        }
+    }
+
+    /// Hibernate type of the override. Without an explicit one, the converter or the basic type
+    /// of the Java type is used. A Java class that is not a basic type has none.
+    pub fn hibernate_type(
+        &self,
+        converters_package: &str,
+        column_type: &dotjava::Type,
+    ) -> Option<String> {
+        if let Some(hibernate) = &self.hibernate {
+            return Some(hibernate.clone());
+        }
+
+        if let Some(converter) = self.converter_class(converters_package, column_type) {
+            return Some(format!("converted::{converter}"));
+        }
+
+        basic_hibernate_type(self.java.as_deref()?).map(|h| h.to_string())
+    }
+}
+
+/// Java type written as part of a class name, e.g. `List<String>` as `ListString`
+fn class_name_part(java: &str) -> String {
+    java.replace(['<', '>', ',', ' ', '[', ']', '.'], "")
+}
+
+/// Hibernate basic type of a Java type, if it is one
+fn basic_hibernate_type(java: &str) -> Option<&'static str> {
+    let hibernate = match java.trim().trim_start_matches("java.lang.") {
+        "Integer" | "int" => "int",
+        "Long" | "long" => "long",
+        "Short" | "short" => "short",
+        "Byte" | "byte" => "byte",
+        "Boolean" | "boolean" => "boolean",
+        "Character" | "char" => "character",
+        "Float" | "float" => "float",
+        "Double" | "double" => "double",
+        "String" => "string",
+        "byte[]" => "binary",
+        "java.math.BigDecimal" => "big_decimal",
+        "java.math.BigInteger" => "big_integer",
+        "java.util.UUID" => "uuid",
+        "java.util.Date" => "timestamp",
+        "java.time.LocalDate" => "LocalDate",
+        "java.time.LocalTime" => "LocalTime",
+        "java.time.LocalDateTime" => "LocalDateTime",
+        "java.time.Instant" => "Instant",
+        "java.time.OffsetDateTime" => "OffsetDateTime",
+        "java.time.ZonedDateTime" => "ZonedDateTime",
+        _ => return None,
+    };
+
+    Some(hibernate)
+}
+
+/// Java type of a fully qualified or `java.lang` name, e.g. `java.util.List<String>`
+fn java_type(name: &str) -> dotjava::Type {
+    const PRIMITIVES: [&str; 8] = [
+        "int", "long", "short", "byte", "boolean", "char", "float", "double",
+    ];
+
+    let name = name.trim();
+
+    if PRIMITIVES.contains(&name) {
+        return dotjava::Type::new_primitive(name.to_string());
+    }
+
+    if let Some((name, generics)) = name.split_once('<')
+        && let Some(generics) = generics.strip_suffix('>')
+    {
+        let mut r#type = java_type(name);
+        let mut depth = 0;
+        let mut start = 0;
+
+        for (i, c) in generics.char_indices() {
+            match c {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                ',' if depth == 0 => {
+                    r#type.add_generic(java_type(&generics[start..i]));
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+
+        r#type.add_generic(java_type(&generics[start..]));
+
+        return r#type;
+    }
+
+    match name.rsplit_once('.') {
+        Some(("java.lang", name)) => dotjava::Type::new(name.to_string(), "".to_string()),
+        Some((package, name)) => dotjava::Type::new(name.to_string(), package.to_string()),
+        None => dotjava::Type::new(name.to_string(), "".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_objects::{ColumnId, KeyType};
+
+    fn column(table: &str, name: &str, r#type: ColumnType) -> Column {
+        Column::new(ColumnId::new(table, name), r#type, true, KeyType::None)
+    }
+
+    #[test]
+    fn test_type_overrides() {
+        let mapping: TypeMapping = r#"
+            [types]
+            "char(1)" = { java = "Boolean", converter = "com.example.YesNoConverter" }
+            "decimal" = { java = "com.example.Money", hibernate = "com.example.MoneyType" }
+
+            [columns]
+            "*.is_*" = { java = "Boolean" }
+            "person.is_admin" = { java = "boolean", hibernate = "yes_no" }
+        "#
+        .parse()
+        .unwrap();
+
+        let java_name = |c: &Column| mapping.column_java_type(c).package_required();
+
+        let active = column("person", "active", ColumnType::Char(1));
+        assert_eq!(java_name(&active), "");
+        assert_eq!(mapping.column_hibernate_type(&active), "converted::com.example.YesNoConverter");
+
+        let code = column("person", "code", ColumnType::Char(3));
+        assert_eq!(mapping.column_hibernate_type(&code), "char");
+
+        let salary = column("person", "salary", ColumnType::Decimal(19, 4));
+        assert_eq!(java_name(&salary), "com.example.Money");
+        assert_eq!(mapping.column_hibernate_type(&salary), "com.example.MoneyType");
+
+        let is_active = column("Invoice", "IS_PAID", ColumnType::Integer(0));
+        assert_eq!(String::from(mapping.column_java_type(&is_active)), "Boolean");
+        assert_eq!(mapping.column_hibernate_type(&is_active), "boolean");
+
+        let is_admin = column("person", "is_admin", ColumnType::Integer(0));
+        assert!(mapping.column_java_type(&is_admin).is_primitive());
+        assert_eq!(mapping.column_hibernate_type(&is_admin), "yes_no");
+    }
+
+    #[test]
+    fn test_invalid_type_overrides() {
+        assert!("[types]\n\"money\" = { java = \"Money\" }".parse::<TypeMapping>().is_err());
+        assert!("[columns]\n\"a.b\" = { type = \"Money\" }".parse::<TypeMapping>().is_err());
+        assert!("[types]\n\"decimal\" = { java = \"com.example.Money\" }".parse::<TypeMapping>().is_err());
+        assert!("[types]\n\"decimal\" = { java = \"java.math.BigDecimal\" }".parse::<TypeMapping>().is_ok());
+    }
+}
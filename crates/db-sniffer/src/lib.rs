@@ -5,17 +5,39 @@ extern crate core;
 use getset::Getters;
 use std::str::FromStr;
 
+mod config;
 mod db_objects;
+mod diagnostics;
 mod sniffers;
 mod error;
 mod naming;
 
 pub mod generators;
 
+pub use config::Config;
+
 pub use db_objects::Table;
+pub use db_objects::TypeProfile;
+
+pub use diagnostics::Diagnostic;
+pub use diagnostics::DiagnosticKind;
+pub use diagnostics::Severity;
 
 pub use error::Error;
+pub use naming::naming_strategy;
+pub use naming::DefaultNaming;
+pub use naming::HungarianNaming;
+pub use naming::Inflection;
+pub use naming::Language;
+pub use naming::NamingStrategy;
+pub use naming::OverridingNaming;
+pub use naming::PreservingNaming;
+pub use naming::PrefixStrippingNaming;
+pub use naming::SanitizedNaming;
 pub use sniffers::sniff;
+pub use sniffers::sniff_with_options;
+pub use sniffers::SniffFilter;
+pub use sniffers::SniffOptions;
 pub use sniffers::SniffResults;
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
\ No newline at end of file
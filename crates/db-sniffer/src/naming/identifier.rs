@@ -0,0 +1,247 @@
+/// Java keywords, literals and the restricted identifiers that can not name a class
+const JAVA_RESERVED_WORDS: &[&str] = &[
+    "_",
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "permits",
+    "private",
+    "protected",
+    "public",
+    "record",
+    "return",
+    "sealed",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "var",
+    "void",
+    "volatile",
+    "while",
+    "yield",
+];
+
+/// Words reserved by SQL Server, MySQL or the SQL standard that are likely column or table names
+const SQL_RESERVED_WORDS: &[&str] = &[
+    "add",
+    "all",
+    "alter",
+    "and",
+    "as",
+    "asc",
+    "between",
+    "by",
+    "case",
+    "check",
+    "column",
+    "constraint",
+    "create",
+    "cross",
+    "current",
+    "database",
+    "default",
+    "delete",
+    "desc",
+    "distinct",
+    "drop",
+    "else",
+    "end",
+    "exists",
+    "file",
+    "for",
+    "foreign",
+    "from",
+    "full",
+    "function",
+    "grant",
+    "group",
+    "having",
+    "identity",
+    "in",
+    "index",
+    "inner",
+    "insert",
+    "into",
+    "is",
+    "join",
+    "key",
+    "keys",
+    "left",
+    "like",
+    "limit",
+    "not",
+    "null",
+    "on",
+    "option",
+    "or",
+    "order",
+    "outer",
+    "percent",
+    "plan",
+    "primary",
+    "procedure",
+    "public",
+    "range",
+    "rank",
+    "read",
+    "references",
+    "right",
+    "row",
+    "rows",
+    "schema",
+    "select",
+    "set",
+    "table",
+    "then",
+    "to",
+    "top",
+    "trigger",
+    "union",
+    "unique",
+    "update",
+    "user",
+    "values",
+    "when",
+    "where",
+];
+
+/// Target language of the generated identifiers
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Language {
+    #[default]
+    Java,
+}
+
+impl Language {
+    fn is_reserved(&self, identifier: &str) -> bool {
+        match self {
+            Language::Java => JAVA_RESERVED_WORDS.contains(&identifier),
+        }
+    }
+
+    /// Valid identifier of the language. The invalid characters are replaced by `_`, the
+    /// identifiers starting with a digit are prefixed with `_` and the reserved words are
+    /// suffixed with `_`.
+    pub fn sanitize(&self, identifier: &str) -> String {
+        let mut sanitized = identifier
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+
+        if sanitized.is_empty() || sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+            sanitized.insert(0, '_');
+        }
+
+        if self.is_reserved(&sanitized) {
+            sanitized.push('_');
+        }
+
+        sanitized
+    }
+}
+
+/// The name must be quoted in SQL: it is a reserved word, has characters other than letters,
+/// digits and `_`, or starts with a digit. With `quote_mixed_case`, also when it has upper and
+/// lower case letters, whose case some databases only keep when quoted.
+pub(crate) fn needs_sql_quoting(name: &str, quote_mixed_case: bool) -> bool {
+    let lowercase = name.to_lowercase();
+
+    let reserved = SQL_RESERVED_WORDS.contains(&lowercase.as_str());
+    let mixed_case = quote_mixed_case
+        && name.chars().any(|c| c.is_uppercase())
+        && name.chars().any(|c| c.is_lowercase());
+    let invalid = name.starts_with(|c: char| c.is_ascii_digit())
+        || name.chars().any(|c| !(c.is_alphanumeric() || c == '_'));
+
+    reserved || mixed_case || invalid
+}
+
+/// The name between the delimiters when it must be quoted
+pub(crate) fn quote_sql(name: &str, open: &str, close: &str, quote_mixed_case: bool) -> String {
+    if needs_sql_quoting(name, quote_mixed_case) {
+        format!("{open}{name}{close}")
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(Language::Java.sanitize("class"), "class_");
+        assert_eq!(Language::Java.sanitize("package"), "package_");
+        assert_eq!(Language::Java.sanitize("Class"), "Class");
+        assert_eq!(Language::Java.sanitize("1stValue"), "_1stValue");
+        assert_eq!(Language::Java.sanitize("unit price"), "unit_price");
+        assert_eq!(Language::Java.sanitize("price$"), "price_");
+        assert_eq!(Language::Java.sanitize(""), "__");
+        assert_eq!(Language::Java.sanitize("while"), "while_");
+    }
+
+    #[test]
+    fn test_quote_sql() {
+        assert_eq!(quote_sql("order", "`", "`", false), "`order`");
+        assert_eq!(quote_sql("USER", "[", "]", false), "[USER]");
+        assert_eq!(quote_sql("FirstName", "`", "`", false), "FirstName");
+        assert_eq!(quote_sql("Order", "[", "]", false), "[Order]");
+        assert_eq!(quote_sql("2ndName", "`", "`", false), "`2ndName`");
+        assert_eq!(quote_sql("unit price", "[", "]", false), "[unit price]");
+        assert_eq!(quote_sql("first_name", "`", "`", false), "first_name");
+        assert_eq!(quote_sql("FIRST_NAME", "`", "`", false), "FIRST_NAME");
+
+        // The mixed case names are only quoted on demand
+        assert_eq!(quote_sql("FirstName", "`", "`", true), "`FirstName`");
+        assert_eq!(quote_sql("first_name", "`", "`", true), "first_name");
+        assert_eq!(quote_sql("FIRST_NAME", "[", "]", true), "FIRST_NAME");
+        assert_eq!(quote_sql("order", "[", "]", true), "[order]");
+    }
+}
@@ -0,0 +1,298 @@
+use crate::naming;
+
+/// Words with the same singular and plural
+const UNCOUNTABLES: [&str; 14] = [
+    "data",
+    "deer",
+    "equipment",
+    "feedback",
+    "fish",
+    "information",
+    "media",
+    "metadata",
+    "news",
+    "series",
+    "sheep",
+    "software",
+    "species",
+    "staff",
+];
+
+/// Singular and plural of the words not following the rules
+const IRREGULARS: [(&str, &str); 45] = [
+    ("alias", "aliases"),
+    ("analysis", "analyses"),
+    ("axis", "axes"),
+    ("bonus", "bonuses"),
+    ("bus", "buses"),
+    ("cache", "caches"),
+    ("calf", "calves"),
+    ("campus", "campuses"),
+    ("census", "censuses"),
+    ("child", "children"),
+    ("cookie", "cookies"),
+    ("crisis", "crises"),
+    ("criterion", "criteria"),
+    ("diagnosis", "diagnoses"),
+    ("echo", "echoes"),
+    ("emu", "emus"),
+    ("foot", "feet"),
+    ("gas", "gases"),
+    ("goose", "geese"),
+    ("guru", "gurus"),
+    ("haiku", "haikus"),
+    ("half", "halves"),
+    ("hero", "heroes"),
+    ("hypothesis", "hypotheses"),
+    ("knife", "knives"),
+    ("leaf", "leaves"),
+    ("life", "lives"),
+    ("man", "men"),
+    ("matrix", "matrices"),
+    ("menu", "menus"),
+    ("mouse", "mice"),
+    ("movie", "movies"),
+    ("ox", "oxen"),
+    ("person", "people"),
+    ("potato", "potatoes"),
+    ("quiz", "quizzes"),
+    ("shelf", "shelves"),
+    ("sku", "skus"),
+    ("status", "statuses"),
+    ("thesis", "theses"),
+    ("thief", "thieves"),
+    ("tooth", "teeth"),
+    ("vertex", "vertices"),
+    ("wife", "wives"),
+    ("woman", "women"),
+];
+
+/// Suffixes of the singulars replaced to get the plural, the first matching applies
+const PLURAL_RULES: [(&str, &str); 6] = [
+    ("sis", "ses"),
+    ("s", "ses"),
+    ("x", "xes"),
+    ("z", "zes"),
+    ("ch", "ches"),
+    ("sh", "shes"),
+];
+
+/// Suffixes of the plurals replaced to get the singular, the first matching applies
+const SINGULAR_RULES: [(&str, &str); 8] = [
+    ("ss", "ss"),
+    ("us", "us"),
+    ("is", "is"),
+    ("sses", "ss"),
+    ("xes", "x"),
+    ("zzes", "zz"),
+    ("ches", "ch"),
+    ("shes", "sh"),
+];
+
+/// English singularization and pluralization of the names. Only the last word of the snake case
+/// or camel case names is inflected, keeping its case.
+#[derive(Clone, Debug, Default)]
+pub struct Inflection {
+    /// Singular and plural of the words overriding the built-in rules, in lowercase
+    exceptions: Vec<(String, String)>,
+}
+
+impl Inflection {
+    /// Overrides the inflection of a word, the same singular and plural make it uncountable
+    pub fn add_exception(&mut self, singular: &str, plural: &str) {
+        self.exceptions
+            .push((singular.to_lowercase(), plural.to_lowercase()));
+    }
+
+    pub fn singularize(&self, name: &str) -> String {
+        inflect_last_word(name, |word| self.singularize_word(word))
+    }
+
+    pub fn pluralize(&self, name: &str) -> String {
+        inflect_last_word(name, |word| self.pluralize_word(word))
+    }
+
+    /// Java class name of the entity of a table
+    pub fn class_name(&self, table_name: &str) -> String {
+        self.singularize(&naming::upper_camel_case(table_name))
+    }
+
+    /// Java name of a property referencing a single entity of the table
+    pub fn property_name(&self, table_name: &str) -> String {
+        self.singularize(&naming::to_lower_camel_case(table_name))
+    }
+
+    fn singularize_word(&self, word: &str) -> String {
+        if let Some(singular) = self.irregular(word, |(singular, plural)| (plural, singular)) {
+            return singular;
+        }
+
+        for (suffix, replacement) in SINGULAR_RULES {
+            if let Some(stem) = word.strip_suffix(suffix) {
+                return format!("{stem}{replacement}");
+            }
+        }
+
+        if word.len() > 4
+            && let Some(stem) = word.strip_suffix("ies")
+            && stem.ends_with(is_consonant)
+        {
+            return format!("{stem}y");
+        }
+
+        word.strip_suffix('s').unwrap_or(word).to_string()
+    }
+
+    fn pluralize_word(&self, word: &str) -> String {
+        if let Some(plural) = self.irregular(word, |(singular, plural)| (singular, plural)) {
+            return plural;
+        }
+
+        for (suffix, replacement) in PLURAL_RULES {
+            if let Some(stem) = word.strip_suffix(suffix) {
+                return format!("{stem}{replacement}");
+            }
+        }
+
+        if let Some(stem) = word.strip_suffix('y')
+            && stem.ends_with(is_consonant)
+        {
+            return format!("{stem}ies");
+        }
+
+        format!("{word}s")
+    }
+
+    /// Inflection of the exceptions, uncountables and irregulars. `from_to` orders a singular
+    /// and plural pair as the word to inflect and its inflection. A word already inflected is
+    /// returned unchanged.
+    fn irregular<'a>(
+        &'a self,
+        word: &str,
+        from_to: fn((&'a str, &'a str)) -> (&'a str, &'a str),
+    ) -> Option<String> {
+        let exceptions = self
+            .exceptions
+            .iter()
+            .map(|(singular, plural)| (singular.as_str(), plural.as_str()));
+        let uncountables = UNCOUNTABLES.iter().map(|&word| (word, word));
+
+        exceptions
+            .chain(uncountables)
+            .chain(IRREGULARS)
+            .map(from_to)
+            .find_map(|(from, to)| {
+                if word == from {
+                    Some(to.to_string())
+                } else if word == to {
+                    Some(word.to_string())
+                } else {
+                    None
+                }
+            })
+    }
+}
+
+fn is_consonant(c: char) -> bool {
+    c.is_ascii_alphabetic() && !"aeiou".contains(c)
+}
+
+/// Applies the inflection to the lowercase last word of the name and restores its case
+fn inflect_last_word(name: &str, inflect: impl Fn(&str) -> String) -> String {
+    let start = last_word_start(name);
+    let (prefix, word) = name.split_at(start);
+
+    if word.is_empty() || !word.chars().any(|c| c.is_alphabetic()) {
+        return name.to_string();
+    }
+
+    let inflected = inflect(&word.to_lowercase());
+
+    let inflected = if word.len() > 1 && !word.chars().any(|c| c.is_lowercase()) {
+        inflected.to_uppercase()
+    } else if word.starts_with(char::is_uppercase) {
+        let mut chars = inflected.chars();
+
+        chars
+            .next()
+            .map(|c| c.to_uppercase().chain(chars).collect())
+            .unwrap_or_default()
+    } else {
+        inflected
+    };
+
+    format!("{prefix}{inflected}")
+}
+
+/// Index of the last word of a snake case or camel case name
+fn last_word_start(name: &str) -> usize {
+    if let Some(i) = name.trim_end_matches('_').rfind('_') {
+        return i + 1;
+    }
+
+    let chars = name.char_indices().collect::<Vec<(usize, char)>>();
+
+    chars
+        .windows(2)
+        .rev()
+        .find(|w| !w[0].1.is_uppercase() && w[1].1.is_uppercase())
+        .map(|w| w[1].0)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_singularize() {
+        let inflection = Inflection::default();
+
+        assert_eq!(inflection.singularize("users"), "user");
+        assert_eq!(inflection.singularize("addresses"), "address");
+        assert_eq!(inflection.singularize("address"), "address");
+        assert_eq!(inflection.singularize("status"), "status");
+        assert_eq!(inflection.singularize("order_statuses"), "order_status");
+        assert_eq!(inflection.singularize("Categories"), "Category");
+        assert_eq!(inflection.singularize("keys"), "key");
+        assert_eq!(inflection.singularize("boxes"), "box");
+        assert_eq!(inflection.singularize("employees"), "employee");
+        assert_eq!(inflection.singularize("People"), "Person");
+        assert_eq!(inflection.singularize("userAnalyses"), "userAnalysis");
+        assert_eq!(inflection.singularize("NEWS"), "NEWS");
+        assert_eq!(inflection.singularize("ORDER_ITEMS"), "ORDER_ITEM");
+        assert_eq!(inflection.singularize("ties"), "tie");
+        assert_eq!(inflection.singularize("menus"), "menu");
+        assert_eq!(inflection.singularize("product_skus"), "product_sku");
+    }
+
+    #[test]
+    fn test_pluralize() {
+        let inflection = Inflection::default();
+
+        assert_eq!(inflection.pluralize("user"), "users");
+        assert_eq!(inflection.pluralize("address"), "addresses");
+        assert_eq!(inflection.pluralize("orderStatus"), "orderStatuses");
+        assert_eq!(inflection.pluralize("category"), "categories");
+        assert_eq!(inflection.pluralize("day"), "days");
+        assert_eq!(inflection.pluralize("branch"), "branches");
+        assert_eq!(inflection.pluralize("person"), "people");
+        assert_eq!(inflection.pluralize("childPerson"), "childPeople");
+        assert_eq!(inflection.pluralize("metadata"), "metadata");
+        assert_eq!(inflection.pluralize("leaf"), "leaves");
+        assert_eq!(inflection.pluralize("menu"), "menus");
+    }
+
+    #[test]
+    fn test_exceptions() {
+        let mut inflection = Inflection::default();
+        inflection.add_exception("person", "persons");
+        inflection.add_exception("Sms", "SMS");
+
+        assert_eq!(inflection.pluralize("person"), "persons");
+        assert_eq!(inflection.singularize("persons"), "person");
+        assert_eq!(inflection.singularize("sent_sms"), "sent_sms");
+        assert_eq!(inflection.class_name("people"), "Person");
+        assert_eq!(inflection.property_name("sent_sms"), "sentSms");
+    }
+}
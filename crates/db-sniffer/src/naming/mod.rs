@@ -1,4 +1,27 @@
+mod identifier;
+mod inflection;
+mod role;
+mod strategy;
+
+pub(crate) use identifier::quote_sql;
+pub use identifier::Language;
+pub use inflection::Inflection;
+pub(crate) use role::{column_role, constraint_role, inverse_qualifier};
+pub use strategy::naming_strategy;
+pub use strategy::DefaultNaming;
+pub use strategy::HungarianNaming;
+pub use strategy::NamingStrategy;
+pub use strategy::OverridingNaming;
+pub use strategy::PreservingNaming;
+pub use strategy::PrefixStrippingNaming;
+pub use strategy::SanitizedNaming;
+
+/// Java class name of a table, with the built-in inflections
 pub fn to_upper_camel_case(s: &str) -> String {
+    Inflection::default().class_name(s)
+}
+
+pub(crate) fn upper_camel_case(s: &str) -> String {
     let mut name = to_lower_camel_case(s).to_string();
 
     name.replace_range(
@@ -11,10 +34,17 @@ pub fn to_upper_camel_case(s: &str) -> String {
             .as_str(),
     );
 
-    remove_plural(name)
+    name
 }
 
 pub fn to_lower_camel_case(s: &str) -> String {
+    // The characters not allowed in identifiers separate the words, like `_`
+    let s = &s.replace(|c: char| !(c.is_alphanumeric() || c == '_'), "_");
+
+    if !s.chars().any(|c| c.is_alphanumeric()) {
+        return s.to_string();
+    }
+
     let mut all_upper = true;
 
     for c in s.chars() {
@@ -62,15 +92,7 @@ pub fn to_lower_camel_case(s: &str) -> String {
             .as_str(),
     );
 
-    remove_plural(name)
-}
-
-fn remove_plural(s: String) -> String {
-    if s.ends_with("s") && !s.ends_with("ss") {
-        return s[..s.len() - 1].to_string();
-    }
-
-    s
+    name
 }
 
 #[cfg(test)]
@@ -80,6 +102,9 @@ mod test {
     #[tokio::test]
     async fn test_to_upper_camel_case() {
         assert_eq!(to_upper_camel_case("users"), "User");
+        assert_eq!(to_upper_camel_case("addresses"), "Address");
+        assert_eq!(to_upper_camel_case("order_statuses"), "OrderStatus");
+        assert_eq!(to_upper_camel_case("categories"), "Category");
         assert_eq!(to_upper_camel_case("user_address"), "UserAddress");
         assert_eq!(to_upper_camel_case("USERS_ADDRESS"), "UsersAddress");
         assert_eq!(to_upper_camel_case("FAMILIAR"), "Familiar");
@@ -92,6 +117,9 @@ mod test {
     #[tokio::test]
     async fn test_to_lower_camel_case() {
         assert_eq!(to_lower_camel_case("user"), "user");
+        assert_eq!(to_lower_camel_case("status"), "status");
+        assert_eq!(to_lower_camel_case("unit price"), "unitPrice");
+        assert_eq!(to_lower_camel_case("1st_value"), "1stValue");
         assert_eq!(to_lower_camel_case("user_address"), "userAddress");
         assert_eq!(to_lower_camel_case("USERS_ADDRESS"), "usersAddress");
         assert_eq!(to_lower_camel_case("UserAddress"), "userAddress");
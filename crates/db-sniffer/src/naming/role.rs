@@ -0,0 +1,177 @@
+use crate::naming::Inflection;
+
+/// Words marking a column or constraint as a key rather than naming its role
+const KEY_WORDS: [&str; 3] = ["id", "fk", "key"];
+
+/// Agent nouns of the roles and the participles qualifying the entities they act on
+const PARTICIPLES: [(&str, &str); 16] = [
+    ("approver", "approved"),
+    ("assignee", "assigned"),
+    ("author", "authored"),
+    ("creator", "created"),
+    ("editor", "edited"),
+    ("manager", "managed"),
+    ("modifier", "modified"),
+    ("owner", "owned"),
+    ("receiver", "received"),
+    ("reporter", "reported"),
+    ("requester", "requested"),
+    ("reviewer", "reviewed"),
+    ("sender", "sent"),
+    ("supervisor", "supervised"),
+    ("updater", "updated"),
+    ("validator", "validated"),
+];
+
+/// Role of the entity referenced by a foreign key column, in snake case: `manager_id` is
+/// `manager` and `createdByUserId` is `created_by_user`. Only the columns named as keys, with
+/// an `id`, `fk` or `key` word first or last, have a role.
+pub(crate) fn column_role(column_name: &str) -> Option<String> {
+    let mut words = words(column_name);
+
+    if words.len() < 2 {
+        return None;
+    }
+
+    if KEY_WORDS.contains(&words[words.len() - 1].as_str()) {
+        words.pop();
+    } else if KEY_WORDS.contains(&words[0].as_str()) {
+        words.remove(0);
+    } else {
+        return None;
+    }
+
+    join(words)
+}
+
+/// Role of the entity referenced by a foreign key constraint, in snake case:
+/// `fk_employee_manager` of the table `employee` is `manager`. The names generated by the
+/// database (`employee_ibfk_1`, `FK__employee__manag__3B75D760`) have no role.
+pub(crate) fn constraint_role(constraint_name: &str, table_name: &str) -> Option<String> {
+    let mut words = words(constraint_name);
+
+    if words
+        .iter()
+        .any(|w| w == "ibfk" || w.chars().any(|c| c.is_ascii_digit()))
+    {
+        return None;
+    }
+
+    words.retain(|w| !KEY_WORDS.contains(&w.as_str()));
+
+    let table_words = self::words(table_name);
+
+    if words.len() > table_words.len() && words.starts_with(&table_words) {
+        words.drain(..table_words.len());
+    }
+
+    join(words)
+}
+
+/// Qualifier of the entities holding a role, in snake case, to name them from the referenced
+/// entity: `manager` of an `employee` qualifies the `managed` employees and `created_by_user`
+/// of a `user` qualifies the `created` documents. `None` when the role is just the referenced
+/// table.
+pub(crate) fn inverse_qualifier(role: &str, ref_table_name: &str) -> Option<String> {
+    let inflection = Inflection::default();
+    let singular = |w: &String| inflection.singularize(w);
+
+    let mut words = words(role);
+    let table_words = words_of_singular(ref_table_name, &inflection);
+
+    if words.len() >= table_words.len()
+        && words[words.len() - table_words.len()..]
+            .iter()
+            .map(singular)
+            .eq(table_words.iter().cloned())
+    {
+        words.truncate(words.len() - table_words.len());
+    }
+
+    if words.last().is_some_and(|w| w == "by") {
+        words.pop();
+    }
+
+    if let Some(last) = words.last_mut()
+        && let Some((_, participle)) = PARTICIPLES.iter().find(|(agent, _)| agent == last)
+    {
+        *last = participle.to_string();
+    }
+
+    join(words)
+}
+
+fn words_of_singular(name: &str, inflection: &Inflection) -> Vec<String> {
+    words(&inflection.singularize(name))
+}
+
+/// Lowercase words of a snake case or camel case name
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut previous_lowercase = false;
+
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            words.push(std::mem::take(&mut word));
+            previous_lowercase = false;
+            continue;
+        }
+
+        if c.is_uppercase() && previous_lowercase {
+            words.push(std::mem::take(&mut word));
+        }
+
+        previous_lowercase = c.is_lowercase() || c.is_ascii_digit();
+        word.extend(c.to_lowercase());
+    }
+
+    words.push(word);
+    words.retain(|w| !w.is_empty());
+    words
+}
+
+fn join(words: Vec<String>) -> Option<String> {
+    if words.is_empty() {
+        None
+    } else {
+        Some(words.join("_"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_role() {
+        assert_eq!(column_role("manager_id"), Some("manager".to_string()));
+        assert_eq!(column_role("createdByUserId"), Some("created_by_user".to_string()));
+        assert_eq!(column_role("ID_PERSON"), Some("person".to_string()));
+        assert_eq!(column_role("dept_no"), None);
+        assert_eq!(column_role("id"), None);
+    }
+
+    #[test]
+    fn test_constraint_role() {
+        assert_eq!(
+            constraint_role("fk_employee_manager", "employee"),
+            Some("manager".to_string())
+        );
+        assert_eq!(
+            constraint_role("FK_Order_BillingAddress", "Order"),
+            Some("billing_address".to_string())
+        );
+        assert_eq!(constraint_role("employee_ibfk_1", "employee"), None);
+        assert_eq!(constraint_role("FK__employee__manag__3B75D760", "employee"), None);
+    }
+
+    #[test]
+    fn test_inverse_qualifier() {
+        assert_eq!(inverse_qualifier("manager", "employee"), Some("managed".to_string()));
+        assert_eq!(inverse_qualifier("created_by_user", "users"), Some("created".to_string()));
+        assert_eq!(inverse_qualifier("billing_address", "address"), Some("billing".to_string()));
+        assert_eq!(inverse_qualifier("customer", "person"), Some("customer".to_string()));
+        assert_eq!(inverse_qualifier("department", "departments"), None);
+    }
+}
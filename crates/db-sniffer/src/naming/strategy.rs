@@ -0,0 +1,352 @@
+use crate::naming::{self, Inflection, Language};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Java names of the database objects
+pub trait NamingStrategy: Debug + Send + Sync {
+    /// Class of the entity of a table
+    fn class_name(&self, table_name: &str) -> String;
+
+    /// Field mapping a column
+    fn field_name(&self, column_name: &str) -> String;
+
+    /// Property referencing a single entity of a table
+    fn property_name(&self, table_name: &str) -> String;
+
+    /// Collection holding the entities of a property
+    fn collection_name(&self, property_name: &str) -> String;
+
+    /// Property referencing a single entity of a table, qualified by its role in the relation
+    /// (`managed` and `employee` are `managedEmployee`)
+    fn qualified_property_name(&self, qualifier: &str, table_name: &str) -> String {
+        naming::to_lower_camel_case(&format!("{qualifier}_{}", self.property_name(table_name)))
+    }
+}
+
+/// Singular upper camel case classes, lower camel case fields and plural collections
+#[derive(Clone, Debug, Default)]
+pub struct DefaultNaming {
+    inflection: Inflection,
+}
+
+impl DefaultNaming {
+    pub fn new(inflection: Inflection) -> Self {
+        DefaultNaming { inflection }
+    }
+}
+
+impl NamingStrategy for DefaultNaming {
+    fn class_name(&self, table_name: &str) -> String {
+        self.inflection.class_name(table_name)
+    }
+
+    fn field_name(&self, column_name: &str) -> String {
+        naming::to_lower_camel_case(column_name)
+    }
+
+    fn property_name(&self, table_name: &str) -> String {
+        self.inflection.property_name(table_name)
+    }
+
+    fn collection_name(&self, property_name: &str) -> String {
+        self.inflection.pluralize(property_name)
+    }
+}
+
+/// The default naming of the tables without their prefix (`tbl_person` is `Person`)
+#[derive(Clone, Debug)]
+pub struct PrefixStrippingNaming {
+    prefixes: Vec<String>,
+    inner: DefaultNaming,
+}
+
+impl PrefixStrippingNaming {
+    pub fn new(inflection: Inflection) -> Self {
+        Self::new_with_prefixes(inflection, &["tbl_", "t_"])
+    }
+
+    pub fn new_with_prefixes(inflection: Inflection, prefixes: &[&str]) -> Self {
+        PrefixStrippingNaming {
+            prefixes: prefixes.iter().map(|p| p.to_lowercase()).collect(),
+            inner: DefaultNaming::new(inflection),
+        }
+    }
+
+    fn strip<'a>(&self, table_name: &'a str) -> &'a str {
+        strip_affix(table_name, &self.prefixes, |name, prefix| {
+            let lowercase = name.to_lowercase();
+            lowercase.starts_with(prefix).then(|| &name[prefix.len()..])
+        })
+    }
+}
+
+impl NamingStrategy for PrefixStrippingNaming {
+    fn class_name(&self, table_name: &str) -> String {
+        self.inner.class_name(self.strip(table_name))
+    }
+
+    fn field_name(&self, column_name: &str) -> String {
+        self.inner.field_name(column_name)
+    }
+
+    fn property_name(&self, table_name: &str) -> String {
+        self.inner.property_name(self.strip(table_name))
+    }
+
+    fn collection_name(&self, property_name: &str) -> String {
+        self.inner.collection_name(property_name)
+    }
+}
+
+/// The default naming of the columns without their Hungarian type suffix (`name_str` is `name`)
+#[derive(Clone, Debug)]
+pub struct HungarianNaming {
+    suffixes: Vec<String>,
+    inner: DefaultNaming,
+}
+
+impl HungarianNaming {
+    pub fn new(inflection: Inflection) -> Self {
+        Self::new_with_suffixes(
+            inflection,
+            &[
+                "_str", "_txt", "_chr", "_int", "_num", "_dec", "_flt", "_dbl", "_dt", "_dtm",
+                "_ts", "_bit", "_bln", "_flg",
+            ],
+        )
+    }
+
+    pub fn new_with_suffixes(inflection: Inflection, suffixes: &[&str]) -> Self {
+        HungarianNaming {
+            suffixes: suffixes.iter().map(|s| s.to_lowercase()).collect(),
+            inner: DefaultNaming::new(inflection),
+        }
+    }
+}
+
+impl NamingStrategy for HungarianNaming {
+    fn class_name(&self, table_name: &str) -> String {
+        self.inner.class_name(table_name)
+    }
+
+    fn field_name(&self, column_name: &str) -> String {
+        let column_name = strip_affix(column_name, &self.suffixes, |name, suffix| {
+            let lowercase = name.to_lowercase();
+            lowercase
+                .ends_with(suffix)
+                .then(|| &name[..name.len() - suffix.len()])
+        });
+
+        self.inner.field_name(column_name)
+    }
+
+    fn property_name(&self, table_name: &str) -> String {
+        self.inner.property_name(table_name)
+    }
+
+    fn collection_name(&self, property_name: &str) -> String {
+        self.inner.collection_name(property_name)
+    }
+}
+
+/// The names of the database, unchanged
+#[derive(Clone, Debug, Default)]
+pub struct PreservingNaming;
+
+impl NamingStrategy for PreservingNaming {
+    fn class_name(&self, table_name: &str) -> String {
+        table_name.to_string()
+    }
+
+    fn field_name(&self, column_name: &str) -> String {
+        column_name.to_string()
+    }
+
+    fn property_name(&self, table_name: &str) -> String {
+        table_name.to_string()
+    }
+
+    fn qualified_property_name(&self, qualifier: &str, table_name: &str) -> String {
+        format!("{qualifier}_{table_name}")
+    }
+
+    fn collection_name(&self, property_name: &str) -> String {
+        property_name.to_string()
+    }
+}
+
+/// The classes of some tables given, and their properties named after them; the other names
+/// are the ones of another strategy
+#[derive(Clone, Debug)]
+pub struct OverridingNaming {
+    inner: Arc<dyn NamingStrategy>,
+    /// Class names by lowercase table name
+    class_names: HashMap<String, String>,
+}
+
+impl OverridingNaming {
+    pub fn new(inner: Arc<dyn NamingStrategy>, class_names: &HashMap<String, String>) -> Self {
+        OverridingNaming {
+            inner,
+            class_names: class_names
+                .iter()
+                .map(|(table, class)| (table.to_lowercase(), class.clone()))
+                .collect(),
+        }
+    }
+
+    fn class_override(&self, table_name: &str) -> Option<&String> {
+        self.class_names.get(&table_name.to_lowercase())
+    }
+}
+
+impl NamingStrategy for OverridingNaming {
+    fn class_name(&self, table_name: &str) -> String {
+        match self.class_override(table_name) {
+            Some(class_name) => class_name.clone(),
+            None => self.inner.class_name(table_name),
+        }
+    }
+
+    fn field_name(&self, column_name: &str) -> String {
+        self.inner.field_name(column_name)
+    }
+
+    fn property_name(&self, table_name: &str) -> String {
+        match self.class_override(table_name) {
+            Some(class_name) => naming::to_lower_camel_case(class_name),
+            None => self.inner.property_name(table_name),
+        }
+    }
+
+    fn collection_name(&self, property_name: &str) -> String {
+        self.inner.collection_name(property_name)
+    }
+
+    fn qualified_property_name(&self, qualifier: &str, table_name: &str) -> String {
+        match self.class_override(table_name) {
+            Some(class_name) => naming::to_lower_camel_case(&format!("{qualifier}_{class_name}")),
+            None => self.inner.qualified_property_name(qualifier, table_name),
+        }
+    }
+}
+
+/// The names of another strategy made valid identifiers of the target language
+#[derive(Clone, Debug)]
+pub struct SanitizedNaming {
+    inner: Arc<dyn NamingStrategy>,
+    language: Language,
+}
+
+impl SanitizedNaming {
+    pub fn new(inner: Arc<dyn NamingStrategy>, language: Language) -> Self {
+        SanitizedNaming { inner, language }
+    }
+}
+
+impl NamingStrategy for SanitizedNaming {
+    fn class_name(&self, table_name: &str) -> String {
+        self.language.sanitize(&self.inner.class_name(table_name))
+    }
+
+    fn field_name(&self, column_name: &str) -> String {
+        self.language.sanitize(&self.inner.field_name(column_name))
+    }
+
+    fn property_name(&self, table_name: &str) -> String {
+        self.language.sanitize(&self.inner.property_name(table_name))
+    }
+
+    fn qualified_property_name(&self, qualifier: &str, table_name: &str) -> String {
+        self.language
+            .sanitize(&self.inner.qualified_property_name(qualifier, table_name))
+    }
+
+    fn collection_name(&self, property_name: &str) -> String {
+        self.language.sanitize(&self.inner.collection_name(property_name))
+    }
+}
+
+/// Naming strategy by its name: `default`, `strip-prefix`, `hungarian` or `preserve`
+pub fn naming_strategy(
+    name: &str,
+    inflection: Inflection,
+) -> Result<Arc<dyn NamingStrategy>, crate::Error> {
+    match name.to_lowercase().as_str() {
+        "default" => Ok(Arc::new(DefaultNaming::new(inflection))),
+        "strip-prefix" => Ok(Arc::new(PrefixStrippingNaming::new(inflection))),
+        "hungarian" => Ok(Arc::new(HungarianNaming::new(inflection))),
+        "preserve" => Ok(Arc::new(PreservingNaming)),
+        _ => Err(crate::Error::ConfigError(format!(
+            "unknown naming strategy {name}"
+        ))),
+    }
+}
+
+/// The name without the first matching affix, unless nothing would be left
+fn strip_affix<'a>(
+    name: &'a str,
+    affixes: &[String],
+    strip: impl Fn(&'a str, &str) -> Option<&'a str>,
+) -> &'a str {
+    affixes
+        .iter()
+        .filter_map(|affix| strip(name, affix))
+        .find(|stripped| !stripped.is_empty())
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_naming_strategies() {
+        let default = DefaultNaming::default();
+        assert_eq!(default.class_name("order_items"), "OrderItem");
+        assert_eq!(default.field_name("first_name"), "firstName");
+        assert_eq!(default.property_name("order_items"), "orderItem");
+        assert_eq!(default.collection_name("orderItem"), "orderItems");
+        assert_eq!(
+            default.qualified_property_name("managed", "employees"),
+            "managedEmployee"
+        );
+
+        let prefix = PrefixStrippingNaming::new(Inflection::default());
+        assert_eq!(prefix.class_name("tbl_people"), "Person");
+        assert_eq!(prefix.class_name("T_ORDER"), "Order");
+        assert_eq!(prefix.class_name("tbl_"), "Tbl");
+        assert_eq!(prefix.property_name("t_customers"), "customer");
+        assert_eq!(prefix.field_name("t_value"), "tValue");
+
+        let hungarian = HungarianNaming::new(Inflection::default());
+        assert_eq!(hungarian.field_name("name_str"), "name");
+        assert_eq!(hungarian.field_name("BIRTH_DATE_DT"), "birthDate");
+        assert_eq!(hungarian.field_name("amount"), "amount");
+
+        let preserve = PreservingNaming;
+        assert_eq!(preserve.class_name("order_items"), "order_items");
+        assert_eq!(preserve.collection_name("order_items"), "order_items");
+        assert_eq!(preserve.qualified_property_name("managed", "staff"), "managed_staff");
+
+        let sanitized = SanitizedNaming::new(Arc::new(DefaultNaming::default()), Language::Java);
+        assert_eq!(sanitized.field_name("class"), "class_");
+        assert_eq!(sanitized.field_name("1st_value"), "_1stValue");
+        assert_eq!(sanitized.field_name("unit price"), "unitPrice");
+        assert_eq!(sanitized.class_name("order_items"), "OrderItem");
+
+        let sanitized = SanitizedNaming::new(Arc::new(PreservingNaming), Language::Java);
+        assert_eq!(sanitized.field_name("unit price"), "unit_price");
+
+        let class_names = HashMap::from([("CUSTOMER".to_string(), "Client".to_string())]);
+        let overriding = OverridingNaming::new(Arc::new(DefaultNaming::default()), &class_names);
+        assert_eq!(overriding.class_name("customer"), "Client");
+        assert_eq!(overriding.property_name("customer"), "client");
+        assert_eq!(overriding.qualified_property_name("billed", "customer"), "billedClient");
+        assert_eq!(overriding.class_name("orders"), "Order");
+
+        assert!(naming_strategy("hungarian", Inflection::default()).is_ok());
+        assert!(naming_strategy("snake", Inflection::default()).is_err());
+    }
+}
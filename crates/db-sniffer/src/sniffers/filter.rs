@@ -0,0 +1,133 @@
+use glob::{MatchOptions, Pattern};
+use regex::Regex;
+
+/// Tables and columns to introspect. Patterns are case-insensitive globs (`hr_*`) or regular
+/// expressions between slashes (`/^tmp_\d+$/`). Column patterns are `table.column`, a pattern
+/// without a table applies to the columns of every table.
+#[derive(Clone, Debug, Default)]
+pub struct SniffFilter {
+    include_tables: Vec<NamePattern>,
+    exclude_tables: Vec<NamePattern>,
+    include_columns: Vec<NamePattern>,
+    exclude_columns: Vec<NamePattern>,
+}
+
+impl SniffFilter {
+    pub fn add_table_include(&mut self, pattern: &str) -> Result<(), crate::Error> {
+        self.include_tables.push(NamePattern::new(pattern)?);
+        Ok(())
+    }
+
+    pub fn add_table_exclude(&mut self, pattern: &str) -> Result<(), crate::Error> {
+        self.exclude_tables.push(NamePattern::new(pattern)?);
+        Ok(())
+    }
+
+    pub fn add_column_include(&mut self, pattern: &str) -> Result<(), crate::Error> {
+        self.include_columns.push(NamePattern::new_qualified(pattern)?);
+        Ok(())
+    }
+
+    pub fn add_column_exclude(&mut self, pattern: &str) -> Result<(), crate::Error> {
+        self.exclude_columns.push(NamePattern::new_qualified(pattern)?);
+        Ok(())
+    }
+
+    /// A table is introspected when it matches an include pattern, or there are none, and no
+    /// exclude pattern
+    pub fn is_table_included(&self, table: &str) -> bool {
+        is_included(&self.include_tables, &self.exclude_tables, table)
+    }
+
+    pub fn is_column_included(&self, table: &str, column: &str) -> bool {
+        is_included(
+            &self.include_columns,
+            &self.exclude_columns,
+            &format!("{table}.{column}"),
+        )
+    }
+}
+
+fn is_included(include: &[NamePattern], exclude: &[NamePattern], name: &str) -> bool {
+    (include.is_empty() || include.iter().any(|p| p.matches(name)))
+        && !exclude.iter().any(|p| p.matches(name))
+}
+
+#[derive(Clone, Debug)]
+enum NamePattern {
+    Glob(Pattern),
+    Regex(Regex),
+}
+
+impl NamePattern {
+    fn new(pattern: &str) -> Result<Self, crate::Error> {
+        let pattern = pattern.trim();
+
+        if pattern.len() > 1 && pattern.starts_with('/') && pattern.ends_with('/') {
+            let regex = &pattern[1..pattern.len() - 1];
+
+            return Regex::new(&format!("(?i)^(?:{regex})$"))
+                .map(NamePattern::Regex)
+                .map_err(|e| {
+                    crate::Error::ConfigError(format!("invalid filter regex {pattern}: {e}"))
+                });
+        }
+
+        Pattern::new(pattern)
+            .map(NamePattern::Glob)
+            .map_err(|e| crate::Error::ConfigError(format!("invalid filter pattern {pattern}: {e}")))
+    }
+
+    /// Pattern of a `table.column` name, `*.` is prepended to the patterns without a table
+    fn new_qualified(pattern: &str) -> Result<Self, crate::Error> {
+        let pattern = pattern.trim();
+
+        if pattern.starts_with('/') || pattern.contains('.') {
+            Self::new(pattern)
+        } else {
+            Self::new(&format!("*.{pattern}"))
+        }
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            NamePattern::Glob(pattern) => pattern.matches_with(
+                name,
+                MatchOptions {
+                    case_sensitive: false,
+                    ..MatchOptions::default()
+                },
+            ),
+            NamePattern::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_filter() {
+        let mut filter = SniffFilter::default();
+        filter.add_table_include("hr_*").unwrap();
+        filter.add_table_exclude("*_bak").unwrap();
+        filter.add_table_exclude(r"/hr_tmp\d+/").unwrap();
+        filter.add_column_exclude("legacy_*").unwrap();
+        filter.add_column_exclude("hr_person.ssn").unwrap();
+
+        assert!(filter.is_table_included("hr_person"));
+        assert!(filter.is_table_included("HR_Department"));
+        assert!(!filter.is_table_included("hr_person_bak"));
+        assert!(!filter.is_table_included("hr_tmp01"));
+        assert!(!filter.is_table_included("flyway_schema_history"));
+
+        assert!(filter.is_column_included("hr_person", "name"));
+        assert!(!filter.is_column_included("hr_person", "ssn"));
+        assert!(filter.is_column_included("hr_department", "ssn"));
+        assert!(!filter.is_column_included("hr_department", "legacy_code"));
+
+        assert!(SniffFilter::default().is_table_included("anything"));
+        assert!(filter.add_table_include("/hr_(/").is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use crate::db_objects::{ColumnId, Database};
+use crate::naming;
+use crate::sniffers::{introspect_rel, ForeignKey, Sniffer};
+use std::mem;
+
+/// Adds to the database the relations suggested by the `<table>_id` and `id_<table>` column
+/// naming conventions. The relations are flagged as inferred so they can be told apart from
+/// the declared ones.
+pub(super) async fn infer_relations(
+    sniffer: &mut (impl Sniffer + ?Sized),
+    database: &mut Database,
+    validate: bool,
+) -> Result<(), crate::Error> {
+    for (from, to) in candidate_relations(database) {
+        let (Some(from_id), Some(to_id)) = (from.first(), to.first()) else {
+            continue;
+        };
+
+        if validate && count_orphans(sniffer, from_id, to_id).await? > 0 {
+            continue;
+        }
+
+        let table_name = from_id.table().to_string();
+
+        let mut relation = introspect_rel(sniffer, from, to, true).await?;
+        relation.set_inferred(true);
+
+        if let Some(table) = database.table_mut(&table_name) {
+            table.add_reference_to(relation);
+        }
+    }
+
+    Ok(())
+}
+
+fn candidate_relations(database: &Database) -> Vec<ForeignKey> {
+    let mut candidates = Vec::new();
+
+    for table in database.tables() {
+        for column in table.columns() {
+            if table.is_col_fk(column.name()) {
+                continue;
+            }
+
+            let Some(referenced_name) = referenced_table_name(column.name()) else {
+                continue;
+            };
+
+            let Some(referenced_table) = database.tables().iter().find(|t| {
+                naming::to_upper_camel_case(t.name())
+                    .eq_ignore_ascii_case(&naming::to_upper_camel_case(referenced_name))
+            }) else {
+                continue;
+            };
+
+            let referenced_ids = referenced_table.ids();
+
+            // Only single column keys can be matched by a single column
+            if referenced_ids.len() != 1 {
+                continue;
+            }
+
+            let referenced_id = referenced_ids[0];
+
+            if referenced_id.table() == column.table() && referenced_id.name() == column.name() {
+                continue;
+            }
+
+            if mem::discriminant(referenced_id.r#type()) != mem::discriminant(column.r#type()) {
+                continue;
+            }
+
+            candidates.push((
+                vec![ColumnId::new(column.table(), column.name())],
+                vec![ColumnId::new(referenced_id.table(), referenced_id.name())],
+            ));
+        }
+    }
+
+    candidates
+}
+
+/// Extracts the referenced table name from a column named `<table>_id` or `id_<table>`
+fn referenced_table_name(column_name: &str) -> Option<&str> {
+    let lowercase = column_name.to_lowercase();
+
+    let name = if lowercase.ends_with("_id") {
+        &column_name[..column_name.len() - 3]
+    } else if lowercase.starts_with("id_") {
+        &column_name[3..]
+    } else {
+        return None;
+    };
+
+    if name.trim_matches('_').is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+async fn count_orphans(
+    sniffer: &mut (impl Sniffer + ?Sized),
+    from: &ColumnId,
+    to: &ColumnId,
+) -> Result<i32, crate::Error> {
+    let sql = format!(
+        r#"
+        select count(*)
+            from {from_table} f
+            where f.{from_col} is not null
+                and not exists (select 1 from {to_table} t where t.{to_col} = f.{from_col});"#,
+        from_table = sniffer.quote_identifier(from.table()),
+        from_col = sniffer.quote_identifier(from.name()),
+        to_table = sniffer.quote_identifier(to.table()),
+        to_col = sniffer.quote_identifier(to.name()),
+    );
+
+    Ok(sniffer
+        .query(&sql)
+        .await?
+        .first()
+        .map(|row| row.get::<i32>(0))
+        .transpose()?
+        .unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_objects::{Column, ColumnType, GenerationType, KeyType, Relation, RelationType, Table};
+
+    fn column(table: &str, name: &str, r#type: ColumnType, key: KeyType) -> Column {
+        Column::new(ColumnId::new(table, name), r#type, true, key)
+    }
+
+    fn database() -> Database {
+        let mut database = Database::new("test_db");
+
+        let mut department = Table::new("departments");
+        department.add_column(column(
+            "departments",
+            "id",
+            ColumnType::Integer(0),
+            KeyType::Primary(GenerationType::AutoIncrement),
+        ));
+        database.add_table(department);
+
+        let mut person = Table::new("Person");
+        person.add_column(column(
+            "Person",
+            "person_id",
+            ColumnType::Integer(0),
+            KeyType::Primary(GenerationType::AutoIncrement),
+        ));
+        person.add_column(column("Person", "department_id", ColumnType::Integer(0), KeyType::None));
+        person.add_column(column("Person", "id_person", ColumnType::Varchar(20), KeyType::None));
+        database.add_table(person);
+
+        let mut phone = Table::new("Phone");
+        phone.add_column(column(
+            "Phone",
+            "id",
+            ColumnType::Integer(0),
+            KeyType::Primary(GenerationType::AutoIncrement),
+        ));
+        phone.add_column(column("Phone", "ID_PERSON", ColumnType::Integer(0), KeyType::None));
+        phone.add_column(column("Phone", "country_id", ColumnType::Integer(0), KeyType::None));
+        phone.add_column(column("Phone", "_id", ColumnType::Integer(0), KeyType::None));
+        database.add_table(phone);
+
+        database
+    }
+
+    #[test]
+    fn test_referenced_table_name() {
+        assert_eq!(referenced_table_name("person_id"), Some("person"));
+        assert_eq!(referenced_table_name("PERSON_ID"), Some("PERSON"));
+        assert_eq!(referenced_table_name("id_person"), Some("person"));
+        assert_eq!(referenced_table_name("user_address_id"), Some("user_address"));
+        assert_eq!(referenced_table_name("id"), None);
+        assert_eq!(referenced_table_name("_id"), None);
+        assert_eq!(referenced_table_name("identifier"), None);
+    }
+
+    #[test]
+    fn test_candidate_relations() {
+        let candidates = candidate_relations(&database());
+
+        assert_eq!(
+            candidates,
+            vec![
+                (
+                    vec![ColumnId::new("Person", "department_id")],
+                    vec![ColumnId::new("departments", "id")]
+                ),
+                (
+                    vec![ColumnId::new("Phone", "ID_PERSON")],
+                    vec![ColumnId::new("Person", "person_id")]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_candidate_relations_skip_declared() {
+        let mut database = database();
+
+        database
+            .table_mut("Phone")
+            .unwrap()
+            .add_reference_to(Relation::new(
+                vec![ColumnId::new("Phone", "ID_PERSON")],
+                vec![ColumnId::new("Person", "person_id")],
+                RelationType::ManyToOne,
+            ).unwrap());
+
+        let candidates = candidate_relations(&database);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].0, vec![ColumnId::new("Person", "department_id")]);
+    }
+}
@@ -1,10 +1,16 @@
-use crate::db_objects::{ColumnId, ColumnType, Dbms, GenerationType, KeyType, Metadata};
-use crate::sniffers::{ConnectionParams, RowGetter, Sniffer};
+use crate::db_objects::{
+    ColumnId, ColumnType, Computed, Dbms, GenerationType, KeyType, Metadata, RoutineType,
+};
+use crate::sniffers::{
+    connect_within, default_expr_generation, default_expr_sequence, unwrap_default_expr,
+    ConnectionParams, NamedForeignKey, QueryFuture, RawColumn, RawParameter, RawRoutine, RawTrigger,
+    RowGetter, Sniffer,
+};
 use sqlx::Row;
 use std::future::Future;
 use std::pin::Pin;
 use std::str::FromStr;
-use tiberius::{AuthMethod, Client, Config};
+use tiberius::{AuthMethod, Client, Config, EncryptionLevel, SqlBrowser};
 use tokio::net::TcpStream;
 use tokio_util::compat::{Compat, FuturesAsyncReadCompatExt, TokioAsyncWriteCompatExt};
 
@@ -27,10 +33,6 @@ impl<'a> MSSQLSniffer<'a> {
             .host
             .as_ref()
             .ok_or(crate::Error::MissingParamError("host".to_string()))?;
-        let port = params
-            .port
-            .as_ref()
-            .ok_or(crate::Error::MissingParamError("port".to_string()))?;
         let dbname = params
             .dbname
             .as_ref()
@@ -39,22 +41,42 @@ impl<'a> MSSQLSniffer<'a> {
         let mut config = Config::new();
 
         config.host(host);
-        config.port(*port);
-        config.trust_cert();
+
+        // A given port takes precedence over the one of the named instance
+        match (params.port, &params.instance) {
+            (Some(port), _) => config.port(port),
+            (None, Some(instance)) => config.instance_name(instance),
+            (None, None) => return Err(crate::Error::MissingParamError("port".to_string())),
+        }
+
+        if let Some(encrypt) = params.encrypt {
+            config.encryption(if encrypt {
+                EncryptionLevel::Required
+            } else {
+                EncryptionLevel::Off
+            });
+        }
+
+        if params.trust_server_certificate.unwrap_or(true) {
+            config.trust_cert();
+        }
+
         config.authentication(AuthMethod::sql_server(user, password));
         config.database(dbname);
 
-        config.trust_cert();
+        let connect = async {
+            let tcp = TcpStream::connect_named(&config)
+                .await
+                .map_err(|e| crate::Error::DBConnectionError(e.to_string()))?;
+            tcp.set_nodelay(true)
+                .map_err(|e| crate::Error::DBConnectionError(e.to_string()))?;
 
-        let tcp = TcpStream::connect(config.get_addr())
-            .await
-            .map_err(|e| crate::Error::DBConnectionError(e.to_string()))?;
-        tcp.set_nodelay(true)
-            .map_err(|e| crate::Error::DBConnectionError(e.to_string()))?;
+            Client::connect(config, tcp.compat_write())
+                .await
+                .map_err(|e| crate::Error::DBConnectionError(e.to_string()))
+        };
 
-        let mut client = Client::connect(config, tcp.compat_write())
-            .await
-            .map_err(|e| crate::Error::DBConnectionError(e.to_string()))?;
+        let mut client = connect_within(params, connect).await??;
 
         let sniffer = MSSQLSniffer {
             conn_params: params,
@@ -99,36 +121,39 @@ impl Sniffer for MSSQLSniffer<'_> {
         })
     }
 
-    fn query(&mut self, query: &str) -> Pin<Box<dyn Future<Output = Vec<RowGetter>> + Send + '_>> {
+    fn query(&mut self, query: &str) -> QueryFuture<'_, Vec<RowGetter>> {
         let query = query.to_string();
 
         Box::pin(async move {
-            self.client
+            Ok(self
+                .client
                 .query(query.as_str(), &[])
-                .await
-                .expect("Error fetching data")
+                .await?
                 .into_first_result()
-                .await
-                .expect("Error fetching data")
+                .await?
                 .into_iter()
                 .map(RowGetter::MSSQLRow)
-                .collect()
+                .collect())
         })
     }
 
-    fn query_metadata(&mut self) -> Pin<Box<dyn Future<Output = Option<Metadata>> + Send + '_>> {
-        Box::pin(async move { Some(Metadata::new(Dbms::Mssql)) })
+    fn query_metadata(&mut self) -> QueryFuture<'_, Option<Metadata>> {
+        Box::pin(async move { Ok(Some(Metadata::new(Dbms::Mssql))) })
     }
 
-    fn query_dbs_names(&mut self) -> Pin<Box<dyn Future<Output = Vec<String>> + Send + '_>> {
+    fn query_dbs_names(&mut self) -> QueryFuture<'_, Vec<String>> {
         Box::pin(async move {
-            let db_name = self.conn_params.dbname.as_ref().unwrap().as_str();
+            let db_name = self
+                .conn_params
+                .dbname
+                .as_ref()
+                .ok_or(crate::Error::MissingParamError("dbname".to_string()))?;
 
-            vec![db_name.to_string()]
+            Ok(vec![db_name.to_string()])
         })
     }
 
-    fn query_tab_names(&mut self) -> Pin<Box<dyn Future<Output = Vec<String>> + Send + '_>> {
+    fn query_tab_names(&mut self) -> QueryFuture<'_, Vec<String>> {
         Box::pin(async move {
             self.query(
                 r#"
@@ -137,9 +162,9 @@ impl Sniffer for MSSQLSniffer<'_> {
                     where TABLE_TYPE = 'BASE TABLE'
                     order by TABLE_NAME;"#,
             )
-            .await
+            .await?
             .iter()
-            .map(|row| row.get::<&str>(0).to_string())
+            .map(|row| Ok(row.get::<&str>(0)?.to_string()))
             .collect()
         })
     }
@@ -147,7 +172,7 @@ impl Sniffer for MSSQLSniffer<'_> {
     fn query_col_names(
         &mut self,
         table_name: &str,
-    ) -> Pin<Box<dyn Future<Output = Vec<String>> + Send + '_>> {
+    ) -> QueryFuture<'_, Vec<String>> {
         let table_name = table_name.to_string();
 
         Box::pin(async move {
@@ -158,9 +183,9 @@ impl Sniffer for MSSQLSniffer<'_> {
             WHERE 
                 TABLE_NAME = '{table_name}';"
             ))
-            .await
+            .await?
             .iter()
-            .map(|row| row.get::<&str>(0).to_string())
+            .map(|row| Ok(row.get::<&str>(0)?.to_string()))
             .collect()
         })
     }
@@ -169,7 +194,7 @@ impl Sniffer for MSSQLSniffer<'_> {
         &mut self,
         table_name: &str,
         column_name: &str,
-    ) -> Pin<Box<dyn Future<Output = ColumnType> + Send + '_>> {
+    ) -> QueryFuture<'_, ColumnType> {
         let table_name = table_name.to_string();
         let column_name = column_name.to_string();
 
@@ -187,12 +212,21 @@ impl Sniffer for MSSQLSniffer<'_> {
                 WHERE
                 TABLE_NAME = '{table_name}' AND COLUMN_NAME = '{column_name}'"
             ))
-            .await
+            .await?
             .iter()
-            .map(|row| row.get::<&str>(0).to_string())
-            .collect::<String>();
+            .map(|row| Ok(row.get::<&str>(0)?.to_string()))
+            .collect::<Result<String, crate::Error>>()?;
+
+            // rowversion is still reported with its deprecated name
+            if col_type == "timestamp" {
+                return Ok(ColumnType::RowVersion);
+            }
 
-            ColumnType::from_str(&col_type).unwrap_or_else(|_| panic!("Error parsing column type: {col_type}"))
+            ColumnType::from_str(&col_type).map_err(|_| crate::Error::UnsupportedTypeError {
+                table: table_name,
+                column: column_name,
+                r#type: col_type,
+            })
         })
     }
 
@@ -200,23 +234,23 @@ impl Sniffer for MSSQLSniffer<'_> {
         &mut self,
         table_name: &str,
         column_name: &str,
-    ) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+    ) -> QueryFuture<'_, bool> {
         let table_name = table_name.to_string();
         let column_name = column_name.to_string();
 
         Box::pin(async move {
-            self.query(&format!(
+            Ok(self.query(&format!(
                 "SELECT IS_NULLABLE
             FROM 
                 INFORMATION_SCHEMA.COLUMNS
             WHERE 
                 TABLE_NAME = '{table_name}' AND COLUMN_NAME = '{column_name}'",
             ))
-            .await
+            .await?
             .iter()
             .map(|row| row.get::<&str>(0))
-            .collect::<String>()
-                == *"YES"
+            .collect::<Result<String, crate::Error>>()?
+                == *"YES")
         })
     }
 
@@ -224,22 +258,24 @@ impl Sniffer for MSSQLSniffer<'_> {
         &mut self,
         table_name: &str,
         column_name: &str,
-    ) -> Pin<Box<dyn Future<Output = Option<String>> + Send + '_>> {
+    ) -> QueryFuture<'_, Option<String>> {
         let table_name = table_name.to_string();
         let column_name = column_name.to_string();
 
         Box::pin(async move {
-            self.query(&format!(
+            Ok(self.query(&format!(
                 "SELECT COLUMN_DEFAULT
                         FROM 
                             INFORMATION_SCHEMA.COLUMNS
                         WHERE 
                             TABLE_NAME = '{table_name}' AND COLUMN_NAME = '{column_name}'"
             ))
-            .await
-            .first()?
-            .opt_get::<&str>(0)
-            .map(|s| s.to_string())
+            .await?
+            .first()
+            .map(|row| row.opt_get::<&str>(0))
+            .transpose()?
+            .flatten()
+            .map(|s| s.to_string()))
         })
     }
 
@@ -247,7 +283,7 @@ impl Sniffer for MSSQLSniffer<'_> {
         &mut self,
         table_name: &str,
         column_name: &str,
-    ) -> Pin<Box<dyn Future<Output = KeyType> + Send + '_>> {
+    ) -> QueryFuture<'_, KeyType> {
         let table_name = table_name.to_string();
         let column_name = column_name.to_string();
 
@@ -285,25 +321,21 @@ impl Sniffer for MSSQLSniffer<'_> {
                 FROM
                     KeyColumns
                 WHERE table_name = '{table_name}' and column_name = '{column_name}'"
-                )).await;
+                )).await?;
 
             let field_key = field_key
                 .first()
-                .expect("Error fetching key")
-                .opt_get(0)
+                .ok_or(crate::Error::IntrospectationError(format!(
+                    "key of the column {table_name}.{column_name} not found"
+                )))?
+                .opt_get(0)?
                 .unwrap_or("NO KEY");
 
-            match field_key {
-                "PRI" => {
-                    if self.query_is_col_auto_incr(&table_name, &column_name).await {
-                        KeyType::Primary(GenerationType::AutoIncrement)
-                    } else {
-                        KeyType::Primary(GenerationType::None)
-                    }
-                }
+            Ok(match field_key {
+                "PRI" => KeyType::Primary(self.query_col_generation(&table_name, &column_name).await?),
                 "UNI" => KeyType::Unique,
                 _ => KeyType::None,
-            }
+            })
         })
     }
 
@@ -311,7 +343,7 @@ impl Sniffer for MSSQLSniffer<'_> {
         &mut self,
         table_name: &str,
         column_name: &str,
-    ) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+    ) -> QueryFuture<'_, bool> {
         let table_name = table_name.to_string();
         let column_name = column_name.to_string();
 
@@ -329,22 +361,82 @@ impl Sniffer for MSSQLSniffer<'_> {
                     sys.identity_columns ic ON col.object_id = ic.object_id AND col.column_id = ic.column_id
                 WHERE
                     tab.name = '{table_name}' and col.name = '{column_name}';"))
-                                     .await;
+                                     .await?;
 
             let auto_increment = if let Some(auto_increment) = auto_increment.first() {
-                auto_increment.get(0)
+                auto_increment.get(0)?
             } else {
                 ""
             };
 
-            matches!(auto_increment, "auto_increment")
+            Ok(matches!(auto_increment, "auto_increment"))
+        })
+    }
+
+    fn query_col_generation(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+    ) -> QueryFuture<'_, GenerationType> {
+        let table_name = table_name.to_string();
+        let column_name = column_name.to_string();
+
+        Box::pin(async move {
+            let identity = self
+                .query(&format!(
+                    "SELECT
+                    CAST(ic.seed_value AS bigint),
+                    CAST(ic.increment_value AS bigint)
+                FROM
+                    sys.identity_columns ic
+                        JOIN
+                    sys.tables tab ON ic.object_id = tab.object_id
+                WHERE
+                    tab.name = '{table_name}' and ic.name = '{column_name}';"
+                ))
+                .await?;
+
+            if let Some(row) = identity.first() {
+                return Ok(match (row.get::<i64>(0)?, row.get::<i64>(1)?) {
+                    (1, 1) => GenerationType::AutoIncrement,
+                    (seed, increment) => GenerationType::Identity(seed, increment),
+                });
+            }
+
+            let Some(default) = self.query_col_default(&table_name, &column_name).await? else {
+                return Ok(GenerationType::None);
+            };
+
+            let default = unwrap_default_expr(&default);
+
+            if let Some(sequence) = default_expr_sequence(default) {
+                let (start, increment) = self
+                    .query(&format!(
+                        "SELECT
+                        CAST(start_value AS bigint),
+                        CAST(increment AS bigint)
+                    FROM
+                        sys.sequences
+                    WHERE
+                        name = '{sequence}';"
+                    ))
+                    .await?
+                    .first()
+                    .map(|row| Ok::<_, crate::Error>((row.get::<i64>(0)?, row.get::<i64>(1)?)))
+                    .transpose()?
+                    .unwrap_or((1, 1));
+
+                return Ok(GenerationType::Sequence(sequence, start, increment));
+            }
+
+            Ok(default_expr_generation(default))
         })
     }
 
     fn query_table_references(
         &mut self,
         table_name: &str,
-    ) -> Pin<Box<dyn Future<Output = Vec<(Vec<ColumnId>, Vec<ColumnId>)>> + Send + '_>> {
+    ) -> QueryFuture<'_, Vec<NamedForeignKey>> {
         let table_name = table_name.to_string();
 
         Box::pin(async move {
@@ -352,7 +444,8 @@ impl Sniffer for MSSQLSniffer<'_> {
                 pk_tab.name AS ReferencedTable,
                 pk_col.name AS ReferencedColumn,
                 fk_col.name AS ForeignKeyColumn,
-                fk.object_id AS fk_id
+                fk.object_id AS fk_id,
+                fk.name AS fk_name
             FROM
                 sys.foreign_keys fk
                     INNER JOIN
@@ -372,17 +465,19 @@ impl Sniffer for MSSQLSniffer<'_> {
             let mut relations = Vec::new();
 
             let mut last_fk_id = None;
+            let mut last_fk_name = String::new();
             let mut from = Vec::new();
             let mut to = Vec::new();
 
-            for row in self.query(sql).await {
-                let ref_table_name: &str = row.get(0);
-                let ref_column_name: &str = row.get(1);
-                let column_name: &str = row.get(2);
-                let fk_id: i32 = row.get(3);
+            for row in self.query(sql).await? {
+                let ref_table_name: &str = row.get(0)?;
+                let ref_column_name: &str = row.get(1)?;
+                let column_name: &str = row.get(2)?;
+                let fk_id: i32 = row.get(3)?;
+                let fk_name: &str = row.get(4)?;
 
-                if last_fk_id.is_some() && last_fk_id.unwrap() != fk_id {
-                    relations.push((from, to));
+                if last_fk_id.is_some_and(|last_fk_id| last_fk_id != fk_id) {
+                    relations.push((last_fk_name.clone(), (from, to)));
                     from = Vec::new();
                     to = Vec::new();
                 }
@@ -391,13 +486,250 @@ impl Sniffer for MSSQLSniffer<'_> {
                 to.push(ColumnId::new(ref_table_name, ref_column_name));
 
                 last_fk_id.replace(fk_id);
+                last_fk_name = fk_name.to_string();
             }
 
             if !from.is_empty() {
-                relations.push((from, to));
+                relations.push((last_fk_name, (from, to)));
             }
 
-            relations
+            Ok(relations)
+        })
+    }
+
+    fn query_table_comment(
+        &mut self,
+        table_name: &str,
+    ) -> QueryFuture<'_, Option<String>> {
+        let table_name = table_name.to_string();
+
+        Box::pin(async move {
+            Ok(self.query(&format!(
+                "SELECT CAST(ep.value AS nvarchar(max))
+                FROM
+                    sys.extended_properties ep
+                WHERE
+                    ep.class = 1
+                    AND ep.name = 'MS_Description'
+                    AND ep.major_id = OBJECT_ID('{table_name}')
+                    AND ep.minor_id = 0;"
+            ))
+            .await?
+            .first()
+            .map(|row| row.opt_get::<&str>(0))
+            .transpose()?
+            .flatten()
+            .map(|c| c.to_string()))
+        })
+    }
+
+    fn query_col_comment(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+    ) -> QueryFuture<'_, Option<String>> {
+        let table_name = table_name.to_string();
+        let column_name = column_name.to_string();
+
+        Box::pin(async move {
+            Ok(self.query(&format!(
+                "SELECT CAST(ep.value AS nvarchar(max))
+                FROM
+                    sys.extended_properties ep
+                WHERE
+                    ep.class = 1
+                    AND ep.name = 'MS_Description'
+                    AND ep.major_id = OBJECT_ID('{table_name}')
+                    AND ep.minor_id = COLUMNPROPERTY(OBJECT_ID('{table_name}'), '{column_name}', 'ColumnId');"
+            ))
+            .await?
+            .first()
+            .map(|row| row.opt_get::<&str>(0))
+            .transpose()?
+            .flatten()
+            .map(|c| c.to_string()))
+        })
+    }
+
+    fn query_col_computed(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+    ) -> QueryFuture<'_, Option<Computed>> {
+        let table_name = table_name.to_string();
+        let column_name = column_name.to_string();
+
+        Box::pin(async move {
+            let rows = self
+                .query(&format!(
+                    "SELECT definition, is_persisted
+                    FROM
+                        sys.computed_columns
+                    WHERE
+                        object_id = OBJECT_ID('{table_name}') AND name = '{column_name}';"
+                ))
+                .await?;
+
+            rows.first()
+                .map(|row| Ok(Computed::new(row.get::<&str>(0)?, row.get::<bool>(1)?)))
+                .transpose()
+        })
+    }
+
+    fn query_table_triggers(
+        &mut self,
+        table_name: &str,
+    ) -> QueryFuture<'_, Vec<RawTrigger>> {
+        let table_name = table_name.to_string();
+
+        Box::pin(async move {
+            self.query(&format!(
+                "SELECT
+                    tr.name,
+                    CASE WHEN tr.is_instead_of_trigger = 1 THEN 'INSTEAD OF' ELSE 'AFTER' END,
+                    te.type_desc,
+                    OBJECT_DEFINITION(tr.object_id)
+                FROM
+                    sys.triggers tr
+                        JOIN
+                    sys.trigger_events te ON te.object_id = tr.object_id
+                WHERE
+                    tr.parent_id = OBJECT_ID('{table_name}')
+                ORDER BY tr.name;"
+            ))
+            .await?
+            .iter()
+            .map(|row| {
+                Ok((
+                    row.get::<&str>(0)?.to_string(),
+                    row.get::<&str>(1)?.to_string(),
+                    row.get::<&str>(2)?.to_string(),
+                    row.opt_get::<&str>(3)?.unwrap_or_default().to_string(),
+                ))
+            })
+            .collect()
         })
     }
+
+    fn query_routines(
+        &mut self,
+    ) -> QueryFuture<'_, Vec<RawRoutine>> {
+        Box::pin(async move {
+            self.query(
+                "SELECT ROUTINE_NAME, ROUTINE_TYPE
+                FROM
+                    INFORMATION_SCHEMA.ROUTINES
+                WHERE
+                    OBJECTPROPERTY(OBJECT_ID(ROUTINE_SCHEMA + '.' + ROUTINE_NAME), 'IsMSShipped') = 0
+                ORDER BY ROUTINE_NAME;",
+            )
+            .await?
+            .iter()
+            .map(|row| {
+                let routine_type = match row.get::<&str>(1)? {
+                    "FUNCTION" => RoutineType::Function,
+                    _ => RoutineType::Procedure,
+                };
+
+                Ok((row.get::<&str>(0)?.to_string(), routine_type))
+            })
+            .collect()
+        })
+    }
+
+    fn query_routine_params(
+        &mut self,
+        routine_name: &str,
+    ) -> QueryFuture<'_, Vec<RawParameter>> {
+        let routine_name = routine_name.to_string();
+
+        Box::pin(async move {
+            self.query(&format!(
+                "SELECT
+                    PARAMETER_NAME,
+                    CASE
+                        WHEN CHARACTER_MAXIMUM_LENGTH IS NOT NULL THEN CONCAT(DATA_TYPE, '(', CHARACTER_MAXIMUM_LENGTH, ')')
+                        WHEN NUMERIC_PRECISION IS NOT NULL AND NUMERIC_SCALE IS NOT NULL THEN CONCAT(DATA_TYPE, '(', NUMERIC_PRECISION, ', ', NUMERIC_SCALE, ')')
+                        ELSE DATA_TYPE
+                    END,
+                    PARAMETER_MODE
+                FROM
+                    INFORMATION_SCHEMA.PARAMETERS
+                WHERE
+                    SPECIFIC_NAME = '{routine_name}' AND IS_RESULT = 'NO'
+                ORDER BY ORDINAL_POSITION;"
+            ))
+            .await?
+            .iter()
+            .map(|row| {
+                Ok((
+                    row.get::<&str>(0)?.to_string(),
+                    row.get::<&str>(1)?.to_string(),
+                    row.get::<&str>(2)?.to_string(),
+                ))
+            })
+            .collect()
+        })
+    }
+
+    fn query_routine_return_type(
+        &mut self,
+        routine_name: &str,
+    ) -> QueryFuture<'_, Option<String>> {
+        let routine_name = routine_name.to_string();
+
+        Box::pin(async move {
+            Ok(self.query(&format!(
+                "SELECT
+                    CASE
+                        WHEN CHARACTER_MAXIMUM_LENGTH IS NOT NULL THEN CONCAT(DATA_TYPE, '(', CHARACTER_MAXIMUM_LENGTH, ')')
+                        WHEN NUMERIC_PRECISION IS NOT NULL AND NUMERIC_SCALE IS NOT NULL THEN CONCAT(DATA_TYPE, '(', NUMERIC_PRECISION, ', ', NUMERIC_SCALE, ')')
+                        ELSE DATA_TYPE
+                    END
+                FROM
+                    INFORMATION_SCHEMA.PARAMETERS
+                WHERE
+                    SPECIFIC_NAME = '{routine_name}' AND IS_RESULT = 'YES';"
+            ))
+            .await?
+            .first()
+            .map(|row| row.opt_get::<&str>(0))
+            .transpose()?
+            .flatten()
+            .map(|t| t.to_string()))
+        })
+    }
+
+    fn query_routine_result_columns(
+        &mut self,
+        routine_name: &str,
+    ) -> QueryFuture<'_, Vec<RawColumn>> {
+        let routine_name = routine_name.to_string();
+
+        Box::pin(async move {
+            // Only the procedures have a result set that can be described
+            self.query(&format!(
+                "SELECT name, system_type_name
+                FROM
+                    sys.dm_exec_describe_first_result_set_for_object(OBJECT_ID('{routine_name}'), 0)
+                WHERE
+                    is_hidden = 0 AND name IS NOT NULL
+                    AND OBJECTPROPERTY(OBJECT_ID('{routine_name}'), 'IsProcedure') = 1
+                ORDER BY column_ordinal;"
+            ))
+            .await?
+            .iter()
+            .map(|row| {
+                Ok((
+                    row.get::<&str>(0)?.to_string(),
+                    row.get::<&str>(1)?.to_string(),
+                ))
+            })
+            .collect()
+        })
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("[{}]", name.replace(']', "]]"))
+    }
 }
@@ -1,8 +1,13 @@
 use crate::db_objects::{
-    ColumnId, ColumnType, Dbms, GenerationType, KeyType, Metadata,
+    ColumnId, ColumnType, Computed, Dbms, GenerationType, KeyType, Metadata, RoutineType,
 };
 use crate::error::Error::MissingParamError;
-use crate::sniffers::{ConnectionParams, RowGetter, Sniffer};
+use crate::sniffers::{
+    connect_within, default_expr_generation, default_expr_sequence, ConnectionParams,
+    NamedForeignKey, QueryFuture, RawColumn, RawParameter, RawRoutine, RawTrigger, RowGetter,
+    Sniffer,
+};
+use sqlx::mysql::{MySqlConnectOptions, MySqlSslMode};
 use sqlx::{Connection, Executor, MySqlConnection, Row};
 use std::future::Future;
 use std::pin::Pin;
@@ -36,11 +41,29 @@ impl<'a> MySQLSniffer<'a> {
             .as_ref()
             .ok_or(MissingParamError("dbname".to_string()))?;
 
-        let connection = MySqlConnection::connect(&format!(
-            "mysql://{}:{}@{}:{}/{}",
-            user, password, host, port, dbname
-        ))
-        .await?;
+        if params.instance.is_some() {
+            return Err(crate::Error::InvalidConnStringError(
+                "the instance parameter is only supported by SQL Server".to_string(),
+            ));
+        }
+
+        let mut options = MySqlConnectOptions::new()
+            .host(host)
+            .port(*port)
+            .username(user)
+            .password(password)
+            .database(dbname);
+
+        // Without encryption the certificate is not checked either
+        match (params.encrypt, params.trust_server_certificate) {
+            (Some(false), _) => options = options.ssl_mode(MySqlSslMode::Disabled),
+            (_, Some(false)) => options = options.ssl_mode(MySqlSslMode::VerifyIdentity),
+            (Some(true), _) => options = options.ssl_mode(MySqlSslMode::Required),
+            _ => {}
+        }
+
+        let connection =
+            connect_within(params, MySqlConnection::connect_with(&options)).await??;
 
         let sniffer = MySQLSniffer {
             conn_params: params,
@@ -82,63 +105,63 @@ impl Sniffer for MySQLSniffer<'_> {
         })
     }
 
-    fn query(&mut self, query: &str) -> Pin<Box<dyn Future<Output = Vec<RowGetter>> + Send + '_>> {
+    fn query(&mut self, query: &str) -> QueryFuture<'_, Vec<RowGetter>> {
         let query = query.to_string();
 
         Box::pin(async move {
-            sqlx::query(&query)
+            Ok(sqlx::query(&query)
                 .fetch_all(&mut self.conn)
-                .await
-                .expect("Error fetching data")
+                .await?
                 .into_iter()
                 .map(RowGetter::MySQlRow)
-                .collect()
+                .collect())
         })
     }
 
-    fn query_metadata(&mut self) -> Pin<Box<dyn Future<Output = Option<Metadata>> + Send + '_>> {
+    fn query_metadata(&mut self) -> QueryFuture<'_, Option<Metadata>> {
         Box::pin(async move {
             let dbms = Metadata::new(Dbms::MySQL);
-            Some(dbms)
+            Ok(Some(dbms))
         })
     }
 
-    fn query_dbs_names(&mut self) -> Pin<Box<dyn Future<Output = Vec<String>> + Send + '_>> {
+    fn query_dbs_names(&mut self) -> QueryFuture<'_, Vec<String>> {
         Box::pin(async move {
-            vec![self
+            let dbname = self
                 .conn_params
                 .dbname
                 .as_ref()
-                .unwrap()
-                .as_str()
-                .to_string()]
+                .ok_or(MissingParamError("dbname".to_string()))?;
+
+            Ok(vec![dbname.to_string()])
         })
     }
 
-    fn query_tab_names(&mut self) -> Pin<Box<dyn Future<Output = Vec<String>> + Send + '_>> {
+    fn query_tab_names(&mut self) -> QueryFuture<'_, Vec<String>> {
         Box::pin(async move {
             let mut tables = self.query("show tables")
-                .await
+                .await?
                 .iter()
-                .map(|row| String::from_utf8_lossy(row.get(0)).to_string())
-                .collect::<Vec<String>>();
+                .map(|row| Ok(String::from_utf8_lossy(row.get(0)?).to_string()))
+                .collect::<Result<Vec<String>, crate::Error>>()?;
             
             tables.sort();
-            tables
+            Ok(tables)
         })
     }
 
     fn query_col_names(
         &mut self,
         table_name: &str,
-    ) -> Pin<Box<dyn Future<Output = Vec<String>> + Send + '_>> {
+    ) -> QueryFuture<'_, Vec<String>> {
         let table_name = table_name.to_string();
 
         Box::pin(async move {
-            self.query(format!("describe {}", table_name).as_str())
-                .await
+            self
+                .query(format!("describe {}", table_name).as_str())
+                .await?
                 .iter()
-                .map(|row| row.get::<&str>(0).to_string())
+                .map(|row| Ok(row.get::<&str>(0)?.to_string()))
                 .collect()
         })
     }
@@ -147,24 +170,23 @@ impl Sniffer for MySQLSniffer<'_> {
         &mut self,
         table_name: &str,
         column_name: &str,
-    ) -> Pin<Box<dyn Future<Output = ColumnType> + Send + '_>> {
+    ) -> QueryFuture<'_, ColumnType> {
         let table_name = table_name.to_string();
         let column_name = column_name.to_string();
 
         Box::pin(async move {
-            let col_type = self.query(format!("describe {}", table_name).as_str())
-                .await
-                .iter()
-                .filter_map(|row| {
-                    if row.get::<&str>(0) == column_name {
-                        Some(String::from_utf8_lossy(row.get::<&[u8]>(1)))
-                    } else {
-                        None
-                    }
-                })
-                .collect::<String>();
+            let rows = self.query(format!("describe {}", table_name).as_str()).await?;
+
+            let col_type = match describe_row(&rows, &column_name)? {
+                Some(row) => String::from_utf8_lossy(row.get::<&[u8]>(1)?).to_string(),
+                None => String::new(),
+            };
             
-            ColumnType::from_str(&col_type).expect("Error parsing column type")
+            ColumnType::from_str(&col_type).map_err(|_| crate::Error::UnsupportedTypeError {
+                table: table_name,
+                column: column_name,
+                r#type: col_type,
+            })
         })
     }
 
@@ -172,23 +194,17 @@ impl Sniffer for MySQLSniffer<'_> {
         &mut self,
         table_name: &str,
         column_name: &str,
-    ) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+    ) -> QueryFuture<'_, bool> {
         let table_name = table_name.to_string();
         let column_name = column_name.to_string();
 
         Box::pin(async move {
-            self.query(format!("describe {}", table_name).as_str())
-                .await
-                .iter()
-                .filter_map(|row| {
-                    if row.get::<&str>(0) == column_name {
-                        Some(row.get::<&str>(2).to_string())
-                    } else {
-                        None
-                    }
-                })
-                .collect::<String>()
-                == "YES"
+            let rows = self.query(format!("describe {}", table_name).as_str()).await?;
+
+            Ok(match describe_row(&rows, &column_name)? {
+                Some(row) => row.get::<&str>(2)? == "YES",
+                None => false,
+            })
         })
     }
 
@@ -196,23 +212,17 @@ impl Sniffer for MySQLSniffer<'_> {
         &mut self,
         table_name: &str,
         column_name: &str,
-    ) -> Pin<Box<dyn Future<Output = Option<String>> + Send + '_>> {
+    ) -> QueryFuture<'_, Option<String>> {
         let table_name = table_name.to_string();
         let column_name = column_name.to_string();
 
         Box::pin(async move {
-            self.query(format!("describe {}", table_name).as_str())
-                .await
-                .iter()
-                .filter_map(|row| {
-                    if row.get::<&str>(0) == column_name {
-                        row.opt_get::<&str>(4)
-                    } else {
-                        None
-                    }
-                })
-                .next()
-                .map(|s| s.to_string())
+            let rows = self.query(format!("describe {}", table_name).as_str()).await?;
+
+            Ok(match describe_row(&rows, &column_name)? {
+                Some(row) => row.opt_get::<&str>(4)?.map(|s| s.to_string()),
+                None => None,
+            })
         })
     }
 
@@ -220,35 +230,23 @@ impl Sniffer for MySQLSniffer<'_> {
         &mut self,
         table_name: &str,
         column_name: &str,
-    ) -> Pin<Box<dyn Future<Output = KeyType> + Send + '_>> {
+    ) -> QueryFuture<'_, KeyType> {
         let table_name = table_name.to_string();
         let column_name = column_name.to_string();
 
         Box::pin(async move {
-            let key: String = self
-                .query(format!("describe {}", table_name).as_str())
-                .await
-                .iter()
-                .filter_map(|row| {
-                    if row.get::<&str>(0) == column_name {
-                        Some(String::from_utf8_lossy(row.get::<&[u8]>(3)).to_string())
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-
-            match key.as_str() {
-                "PRI" => {
-                    if self.query_is_col_auto_incr(&table_name, &column_name).await {
-                        KeyType::Primary(GenerationType::AutoIncrement)
-                    } else {
-                        KeyType::Primary(GenerationType::None)
-                    }
-                }
+            let rows = self.query(format!("describe {}", table_name).as_str()).await?;
+
+            let key = match describe_row(&rows, &column_name)? {
+                Some(row) => String::from_utf8_lossy(row.get::<&[u8]>(3)?).to_string(),
+                None => String::new(),
+            };
+
+            Ok(match key.as_str() {
+                "PRI" => KeyType::Primary(self.query_col_generation(&table_name, &column_name).await?),
                 "UNI" => KeyType::Unique,
                 _ => KeyType::None,
-            }
+            })
         })
     }
 
@@ -256,23 +254,51 @@ impl Sniffer for MySQLSniffer<'_> {
         &mut self,
         table_name: &str,
         column_name: &str,
-    ) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+    ) -> QueryFuture<'_, bool> {
         let table_name = table_name.to_string();
         let column_name = column_name.to_string();
 
         Box::pin(async move {
-            self.query(format!("describe {}", table_name).as_str())
-                .await
-                .iter()
-                .filter_map(|row| {
-                    if row.get::<&str>(0) == column_name {
-                        Some(row.get::<&str>(5).to_string())
-                    } else {
-                        None
-                    }
-                })
-                .collect::<String>()
-                == "auto_increment"
+            let rows = self.query(format!("describe {}", table_name).as_str()).await?;
+
+            Ok(match describe_row(&rows, &column_name)? {
+                Some(row) => row.get::<&str>(5)? == "auto_increment",
+                None => false,
+            })
+        })
+    }
+
+    fn query_col_generation(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+    ) -> QueryFuture<'_, GenerationType> {
+        let table_name = table_name.to_string();
+        let column_name = column_name.to_string();
+
+        Box::pin(async move {
+            if self.query_is_col_auto_incr(&table_name, &column_name).await? {
+                return Ok(GenerationType::AutoIncrement);
+            }
+
+            let Some(default) = self.query_col_default(&table_name, &column_name).await? else {
+                return Ok(GenerationType::None);
+            };
+
+            // MariaDB sequences can be queried as if they were tables
+            if let Some(sequence) = default_expr_sequence(&default) {
+                let (start, increment) = self
+                    .query(&format!("SELECT start_value, increment FROM {sequence}"))
+                    .await?
+                    .first()
+                    .map(|row| Ok::<_, crate::Error>((row.get::<i64>(0)?, row.get::<i64>(1)?)))
+                    .transpose()?
+                    .unwrap_or((1, 1));
+
+                return Ok(GenerationType::Sequence(sequence, start, increment));
+            }
+
+            Ok(default_expr_generation(&default))
         })
     }
 
@@ -282,7 +308,7 @@ impl Sniffer for MySQLSniffer<'_> {
     fn query_table_references(
         &mut self,
         table_name: &str,
-    ) -> Pin<Box<dyn Future<Output = Vec<(Vec<ColumnId>, Vec<ColumnId>)>> + Send + '_>> {
+    ) -> QueryFuture<'_, Vec<NamedForeignKey>> {
         let table_name = table_name.to_string();
 
         Box::pin(async move {
@@ -306,16 +332,18 @@ impl Sniffer for MySQLSniffer<'_> {
             let mut from = Vec::new();
             let mut to = Vec::new();
 
-            for row in self.query(sql).await.iter() {
-                let ref_table_name: &str = &String::from_utf8_lossy(row.get(0));
-                let ref_column_name: &str = row.get(1);
-                let column_name: &str = row.get(2);
-                let constraint_name: &[u8] = row.get::<&[u8]>(3);
+            let rows = self.query(sql).await?;
+
+            for row in rows.iter() {
+                let ref_table_name: &str = &String::from_utf8_lossy(row.get(0)?);
+                let ref_column_name: &str = row.get(1)?;
+                let column_name: &str = row.get(2)?;
+                let constraint_name: &[u8] = row.get::<&[u8]>(3)?;
 
-                if last_constraint_name.is_some()
-                    && last_constraint_name.unwrap() != constraint_name
+                if let Some(last) = last_constraint_name
+                    && last != constraint_name
                 {
-                    relations.push((from, to));
+                    relations.push((String::from_utf8_lossy(last).to_string(), (from, to)));
                     from = Vec::new();
                     to = Vec::new();
                 }
@@ -330,11 +358,244 @@ impl Sniffer for MySQLSniffer<'_> {
                 last_constraint_name.replace(constraint_name);
             }
 
-            if !from.is_empty() {
-                relations.push((from, to));
+            if let Some(last) = last_constraint_name
+                && !from.is_empty()
+            {
+                relations.push((String::from_utf8_lossy(last).to_string(), (from, to)));
             }
 
-            relations
+            Ok(relations)
+        })
+    }
+
+    fn query_table_comment(
+        &mut self,
+        table_name: &str,
+    ) -> QueryFuture<'_, Option<String>> {
+        let table_name = table_name.to_string();
+
+        Box::pin(async move {
+            Ok(self
+                .query(&format!(
+                    "SELECT TABLE_COMMENT
+                    FROM
+                        INFORMATION_SCHEMA.TABLES
+                    WHERE
+                        TABLE_SCHEMA = DATABASE() AND TABLE_NAME = '{table_name}';"
+                ))
+                .await?
+                .first()
+                .map(|row| row.opt_get::<&[u8]>(0))
+                .transpose()?
+                .flatten()
+                .map(|c| String::from_utf8_lossy(c).to_string()))
         })
     }
+
+    fn query_col_comment(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+    ) -> QueryFuture<'_, Option<String>> {
+        let table_name = table_name.to_string();
+        let column_name = column_name.to_string();
+
+        Box::pin(async move {
+            Ok(self
+                .query(&format!(
+                    "SELECT COLUMN_COMMENT
+                    FROM
+                        INFORMATION_SCHEMA.COLUMNS
+                    WHERE
+                        TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = '{table_name}'
+                        AND COLUMN_NAME = '{column_name}';"
+                ))
+                .await?
+                .first()
+                .map(|row| row.opt_get::<&[u8]>(0))
+                .transpose()?
+                .flatten()
+                .map(|c| String::from_utf8_lossy(c).to_string()))
+        })
+    }
+
+    fn query_col_computed(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+    ) -> QueryFuture<'_, Option<Computed>> {
+        let table_name = table_name.to_string();
+        let column_name = column_name.to_string();
+
+        Box::pin(async move {
+            let rows = self
+                .query(&format!(
+                    "SELECT EXTRA, GENERATION_EXPRESSION
+                    FROM
+                        INFORMATION_SCHEMA.COLUMNS
+                    WHERE
+                        TABLE_SCHEMA = DATABASE()
+                        AND TABLE_NAME = '{table_name}'
+                        AND COLUMN_NAME = '{column_name}';"
+                ))
+                .await?;
+
+            let Some(row) = rows.first() else {
+                return Ok(None);
+            };
+
+            let extra = String::from_utf8_lossy(row.get::<&[u8]>(0)?).to_uppercase();
+
+            // DEFAULT_GENERATED only flags the columns with an expression as default value
+            let stored = if extra.contains("VIRTUAL GENERATED") {
+                false
+            } else if extra.contains("STORED GENERATED") || extra.contains("PERSISTENT GENERATED") {
+                true
+            } else {
+                return Ok(None);
+            };
+
+            Ok(row
+                .opt_get::<&[u8]>(1)?
+                .map(|e| Computed::new(&String::from_utf8_lossy(e), stored)))
+        })
+    }
+
+    fn query_table_triggers(
+        &mut self,
+        table_name: &str,
+    ) -> QueryFuture<'_, Vec<RawTrigger>> {
+        let table_name = table_name.to_string();
+
+        Box::pin(async move {
+            self.query(&format!(
+                "SELECT TRIGGER_NAME, ACTION_TIMING, EVENT_MANIPULATION, ACTION_STATEMENT
+                FROM
+                    INFORMATION_SCHEMA.TRIGGERS
+                WHERE
+                    TRIGGER_SCHEMA = DATABASE() AND EVENT_OBJECT_TABLE = '{table_name}'
+                ORDER BY TRIGGER_NAME;"
+            ))
+            .await?
+            .iter()
+            .map(|row| {
+                Ok((
+                    String::from_utf8_lossy(row.get::<&[u8]>(0)?).to_string(),
+                    String::from_utf8_lossy(row.get::<&[u8]>(1)?).to_string(),
+                    String::from_utf8_lossy(row.get::<&[u8]>(2)?).to_string(),
+                    String::from_utf8_lossy(row.get::<&[u8]>(3)?).to_string(),
+                ))
+            })
+            .collect()
+        })
+    }
+
+    fn query_routines(
+        &mut self,
+    ) -> QueryFuture<'_, Vec<RawRoutine>> {
+        Box::pin(async move {
+            self.query(
+                "SELECT ROUTINE_NAME, ROUTINE_TYPE
+                FROM
+                    INFORMATION_SCHEMA.ROUTINES
+                WHERE
+                    ROUTINE_SCHEMA = DATABASE()
+                ORDER BY ROUTINE_NAME;",
+            )
+            .await?
+            .iter()
+            .map(|row| {
+                let name = String::from_utf8_lossy(row.get::<&[u8]>(0)?).to_string();
+                let routine_type = match &*String::from_utf8_lossy(row.get::<&[u8]>(1)?) {
+                    "FUNCTION" => RoutineType::Function,
+                    _ => RoutineType::Procedure,
+                };
+
+                Ok((name, routine_type))
+            })
+            .collect()
+        })
+    }
+
+    fn query_routine_params(
+        &mut self,
+        routine_name: &str,
+    ) -> QueryFuture<'_, Vec<RawParameter>> {
+        let routine_name = routine_name.to_string();
+
+        Box::pin(async move {
+            self.query(&format!(
+                "SELECT PARAMETER_NAME, DTD_IDENTIFIER, PARAMETER_MODE
+                FROM
+                    INFORMATION_SCHEMA.PARAMETERS
+                WHERE
+                    SPECIFIC_SCHEMA = DATABASE()
+                    AND SPECIFIC_NAME = '{routine_name}'
+                    AND ORDINAL_POSITION > 0
+                ORDER BY ORDINAL_POSITION;"
+            ))
+            .await?
+            .iter()
+            .map(|row| {
+                Ok((
+                    String::from_utf8_lossy(row.get::<&[u8]>(0)?).to_string(),
+                    String::from_utf8_lossy(row.get::<&[u8]>(1)?).to_string(),
+                    String::from_utf8_lossy(row.get::<&[u8]>(2)?).to_string(),
+                ))
+            })
+            .collect()
+        })
+    }
+
+    fn query_routine_return_type(
+        &mut self,
+        routine_name: &str,
+    ) -> QueryFuture<'_, Option<String>> {
+        let routine_name = routine_name.to_string();
+
+        Box::pin(async move {
+            Ok(self.query(&format!(
+                "SELECT DTD_IDENTIFIER
+                FROM
+                    INFORMATION_SCHEMA.PARAMETERS
+                WHERE
+                    SPECIFIC_SCHEMA = DATABASE()
+                    AND SPECIFIC_NAME = '{routine_name}'
+                    AND ORDINAL_POSITION = 0;"
+            ))
+            .await?
+            .first()
+            .map(|row| row.opt_get::<&[u8]>(0))
+            .transpose()?
+            .flatten()
+            .map(|t| String::from_utf8_lossy(t).to_string()))
+        })
+    }
+
+    fn query_routine_result_columns(
+        &mut self,
+        _routine_name: &str,
+    ) -> QueryFuture<'_, Vec<RawColumn>> {
+        // MySQL can not describe the result sets of a procedure without calling it
+        Box::pin(async move { Ok(vec![]) })
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("`{}`", name.replace('`', "``"))
+    }
+}
+
+/// Row of the column in the `describe` of its table
+fn describe_row<'a>(
+    rows: &'a [RowGetter],
+    column_name: &str,
+) -> Result<Option<&'a RowGetter>, crate::Error> {
+    for row in rows {
+        if row.get::<&str>(0)? == column_name {
+            return Ok(Some(row));
+        }
+    }
+
+    Ok(None)
 }
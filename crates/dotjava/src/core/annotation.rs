@@ -0,0 +1,70 @@
+#[derive(Clone)]
+pub struct Annotation {
+    name: String,
+    package: String,
+    parameters: Vec<(String, String)>,
+    /// Imports required by the parameter values, e.g. enum constants
+    imports: Vec<String>,
+}
+
+impl Annotation {
+    pub fn new(name: String, package: String) -> Self {
+        Self {
+            name,
+            package,
+            parameters: vec![],
+            imports: vec![],
+        }
+    }
+
+    /// Adds a parameter. The value is written as is, so literals must include their quotes
+    pub fn add_parameter(&mut self, name: String, value: String) {
+        self.parameters.push((name, value));
+    }
+
+    pub fn with_parameter(mut self, name: &str, value: &str) -> Self {
+        self.add_parameter(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_import(mut self, import: &str) -> Self {
+        self.imports.push(import.to_string());
+        self
+    }
+
+    pub fn package_required(&self) -> String {
+        if self.package.is_empty() {
+            return "".to_string();
+        }
+
+        format!("{}.{}", self.package, self.name)
+    }
+
+    pub fn packages_required(&self) -> Vec<String> {
+        let mut packages = self.imports.clone();
+        packages.push(self.package_required());
+        packages.retain(|p| !p.is_empty());
+        packages
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+}
+
+impl From<Annotation> for String {
+    fn from(value: Annotation) -> Self {
+        if value.parameters.is_empty() {
+            return format!("@{}", value.name);
+        }
+
+        let parameters_string = value
+            .parameters
+            .into_iter()
+            .map(|(name, value)| format!("{name} = {value}"))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        format!("@{}({parameters_string})", value.name)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Class;
+use crate::{Annotation, Class};
 use std::ops::Add;
 
 #[derive(Copy, Clone)]
@@ -8,9 +8,9 @@ pub enum Visibility {
     Private,
 }
 
-impl Into<String> for Visibility {
-    fn into(self) -> String {
-        match self {
+impl From<Visibility> for String {
+    fn from(value: Visibility) -> Self {
+        match value {
             Visibility::Public => "public".to_string(),
             Visibility::Protected => "protected".to_string(),
             Visibility::Private => "private".to_string(),
@@ -23,6 +23,7 @@ pub struct Type {
     name: String,
     package: String,
     generics: Vec<Type>,
+    primitive: bool,
 }
 
 impl Type {
@@ -31,11 +32,15 @@ impl Type {
             name,
             package,
             generics: vec![],
+            primitive: false,
         }
     }
 
     pub fn new_primitive(name: String) -> Self {
-        Self::new(name, "".to_string())
+        Self {
+            primitive: true,
+            ..Self::new(name, "".to_string())
+        }
     }
 
     pub fn string() -> Self {
@@ -78,6 +83,31 @@ impl Type {
         Self::new("void".to_string(), "".to_string())
     }
 
+    pub fn is_primitive(&self) -> bool {
+        self.primitive
+    }
+
+    /// Primitive type of a wrapper, or the same type if it has none
+    pub fn unboxed(&self) -> Self {
+        let primitive = match self.name.as_str() {
+            "Integer" => "int",
+            "Boolean" => "boolean",
+            "Character" => "char",
+            "Byte" => "byte",
+            "Short" => "short",
+            "Long" => "long",
+            "Float" => "float",
+            "Double" => "double",
+            _ => return self.clone(),
+        };
+
+        if !self.package.is_empty() && self.package != "java.lang" {
+            return self.clone();
+        }
+
+        Self::new_primitive(primitive.to_string())
+    }
+
     pub fn package_required(&self) -> String {
         if self.package.is_empty() {
             return "".to_string();
@@ -86,6 +116,18 @@ impl Type {
         format!("{}.{}", self.package, self.name)
     }
 
+    /// Imports of the type and of its generics
+    pub fn packages_required(&self) -> Vec<String> {
+        let mut packages = vec![self.package_required()];
+
+        for generic in self.generics.iter() {
+            packages.extend(generic.packages_required());
+        }
+
+        packages.retain(|p| !p.is_empty());
+        packages
+    }
+
     pub fn package(&self) -> &str {
         &self.package
     }
@@ -95,20 +137,20 @@ impl Type {
     }
 }
 
-impl Into<String> for Type {
-    fn into(self) -> String {
-        if self.generics.is_empty() {
-            return self.name;
+impl From<Type> for String {
+    fn from(value: Type) -> Self {
+        if value.generics.is_empty() {
+            return value.name;
         }
 
-        let generics_string = self
+        let generics_string = value
             .generics
             .into_iter()
             .map(|a| a.into())
             .collect::<Vec<String>>()
             .join(", ");
 
-        format!("{}<{generics_string}>", self.name)
+        format!("{}<{generics_string}>", value.name)
     }
 }
 
@@ -118,6 +160,8 @@ pub struct Field {
     r#type: Type,
     visibility: Option<Visibility>,
     value: Option<String>,
+    annotations: Vec<Annotation>,
+    doc: Option<String>,
 }
 
 impl Field {
@@ -132,6 +176,8 @@ impl Field {
             r#type,
             visibility,
             value,
+            annotations: vec![],
+            doc: None,
         }
     }
 
@@ -139,6 +185,29 @@ impl Field {
         self.r#type.package_required()
     }
 
+    pub fn packages_required(&self) -> Vec<String> {
+        self.r#type.packages_required()
+    }
+
+    pub fn annotations_packages_required(&self) -> Vec<String> {
+        self.annotations
+            .iter()
+            .flat_map(|a| a.packages_required())
+            .collect()
+    }
+
+    pub fn add_annotation(&mut self, annotation: Annotation) {
+        self.annotations.push(annotation);
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn set_doc(&mut self, doc: String) {
+        self.doc = Some(doc);
+    }
+
     pub fn getter(&self) -> Method {
         Method::getter(self)
     }
@@ -156,26 +225,34 @@ impl Field {
     }
 }
 
-impl Into<String> for Field {
-    fn into(self) -> String {
-        let visibility_string = if let Some(a) = self.visibility {
+impl From<Field> for String {
+    fn from(value: Field) -> Self {
+        let visibility_string = if let Some(a) = value.visibility {
             let a: String = a.into();
             format!("{a} ")
         } else {
             "".to_string()
         };
 
-        let value_string = if let Some(a) = self.value {
+        let value_string = if let Some(a) = value.value {
             format!(" = {};", a)
         } else {
             ";".to_string()
         };
 
-        let field_type: String = self.r#type.into();
+        let field_type: String = value.r#type.into();
+
+        let annotations_string = value
+            .annotations
+            .into_iter()
+            .map(|a| format!("{}\n", String::from(a)))
+            .collect::<String>();
+
+        let doc_string = value.doc.map(|d| javadoc(&d)).unwrap_or_default();
 
         format!(
-            "{visibility_string}{} {}{}",
-            field_type, self.name, value_string
+            "{doc_string}{annotations_string}{visibility_string}{} {}{}",
+            field_type, value.name, value_string
         )
     }
 }
@@ -187,6 +264,7 @@ pub struct Method {
     visibility: Option<Visibility>,
     parameters: Vec<(Type, String)>,
     body: Option<String>,
+    doc: Option<String>,
 }
 
 impl Method {
@@ -203,6 +281,7 @@ impl Method {
             visibility,
             parameters,
             body,
+            doc: None,
         }
     }
 
@@ -244,11 +323,7 @@ impl Method {
         let mut body = String::new();
 
         body = body.add(&format!(
-            r#"
-        if (o == null || getClass() != o.getClass()) return false;
-
-        {} that = ({}) o;
-        "#,
+            "if (o == null || getClass() != o.getClass()) return false;\n\n{} that = ({}) o;\n",
             class.name(),
             class.name()
         ));
@@ -275,6 +350,7 @@ impl Method {
             visibility: Some(Visibility::Public),
             parameters: vec![(param, "o".to_string())],
             body: Some(body),
+            doc: None,
         }
     }
 
@@ -302,24 +378,40 @@ impl Method {
             visibility: Some(Visibility::Public),
             parameters: vec![],
             body: Some(body),
+            doc: None,
         }
     }
 
     pub fn package_required(&self) -> String {
         self.r#type.package_required()
     }
+
+    /// Imports of the return and parameter types
+    pub fn packages_required(&self) -> Vec<String> {
+        let mut packages = self.r#type.packages_required();
+
+        for (r#type, _) in self.parameters.iter() {
+            packages.extend(r#type.packages_required());
+        }
+
+        packages
+    }
+
+    pub fn set_doc(&mut self, doc: String) {
+        self.doc = Some(doc);
+    }
 }
 
-impl Into<String> for Method {
-    fn into(self) -> String {
-        let visibility_string = if let Some(a) = self.visibility {
+impl From<Method> for String {
+    fn from(value: Method) -> Self {
+        let visibility_string = if let Some(a) = value.visibility {
             let a: String = a.into();
             format!("{a} ")
         } else {
             "".to_string()
         };
 
-        let parameters_string = self
+        let parameters_string = value
             .parameters
             .into_iter()
             .map(|(r#type, name)| {
@@ -330,17 +422,37 @@ impl Into<String> for Method {
             .collect::<Vec<String>>()
             .join(", ");
 
-        let body_string = if let Some(a) = self.body {
-            format!(" {{\n{}\n}}", a)
+        let body_string = if let Some(a) = value.body {
+            let body = a
+                .lines()
+                .map(|line| format!("    {line}").trim_end().to_string())
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            format!(" {{\n{}\n}}", body)
         } else {
             ";".to_string()
         };
 
-        let return_type: String = self.r#type.into();
+        let return_type: String = value.r#type.into();
+
+        let doc_string = value.doc.map(|d| javadoc(&d)).unwrap_or_default();
 
         format!(
-            "{visibility_string}{return_type} {}({parameters_string}){body_string}",
-            self.name
+            "{doc_string}{visibility_string}{return_type} {}({parameters_string}){body_string}",
+            value.name
         )
     }
 }
+
+/// Formats the text as a Javadoc comment, one line of the text per line of the comment
+pub(crate) fn javadoc(doc: &str) -> String {
+    let lines = doc
+        .replace("*/", "*&#47;")
+        .lines()
+        .map(|line| format!(" * {line}").trim_end().to_string())
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    format!("/**\n{lines}\n */\n")
+}
@@ -1,4 +1,5 @@
-use crate::{Field, Method};
+use crate::core::basic::javadoc;
+use crate::{Annotation, Field, Method, Type};
 use std::collections::HashSet;
 use crate::core::interface::Interface;
 
@@ -8,7 +9,10 @@ pub struct Class {
     fields: Vec<Field>,
     methods: Vec<Method>,
     imports: Vec<String>,
-    interfaces: Vec<Interface>
+    superclass: Option<Type>,
+    interfaces: Vec<Interface>,
+    annotations: Vec<Annotation>,
+    doc: Option<String>,
 }
 
 impl Class {
@@ -21,19 +25,12 @@ impl Class {
         let mut imports = HashSet::new();
 
         for field in fields.iter() {
-            let package = field.package_required();
-            
-            if !package.is_empty() {
-                imports.insert(package);
-            }
+            imports.extend(field.packages_required());
+            imports.extend(field.annotations_packages_required());
         }
         
         for method in methods.iter() {
-            let package = method.package_required();
-            
-            if !package.is_empty() {
-                imports.insert(package);
-            }
+            imports.extend(method.packages_required());
         }
         
         Self {
@@ -42,7 +39,10 @@ impl Class {
             fields,
             methods,
             imports: imports.into_iter().collect(),
-            interfaces: Vec::new()
+            superclass: None,
+            interfaces: Vec::new(),
+            annotations: Vec::new(),
+            doc: None,
         }
     }
     
@@ -63,53 +63,100 @@ impl Class {
         &self.name
     }
     
+    pub fn set_superclass(&mut self, superclass: Type) {
+        self.add_import(superclass.package_required());
+        self.superclass = Some(superclass);
+    }
+
     pub fn add_interface(&mut self, interface: Interface) {
-        self.imports.push(interface.package_required());
+        for package in interface.packages_required() {
+            self.add_import(package);
+        }
+
         self.interfaces.push(interface);
     }
+
+    pub fn add_annotation(&mut self, annotation: Annotation) {
+        for package in annotation.packages_required() {
+            self.add_import(package);
+        }
+
+        self.annotations.push(annotation);
+    }
+
+    pub fn set_doc(&mut self, doc: String) {
+        self.doc = Some(doc);
+    }
+
+    pub fn add_import(&mut self, import: String) {
+        if !import.is_empty() && !self.imports.contains(&import) {
+            self.imports.push(import);
+        }
+    }
 }
 
-impl Into<String> for Class {
-    fn into(self) -> String {
+impl From<Class> for String {
+    fn from(value: Class) -> Self {
         let mut imports = String::new();
 
-        for import in self.imports {
+        for import in value.imports {
             imports.push_str(&format!("import {};\n", import));
         }
 
         let mut fields = String::new();
 
-        for field in self.fields {
-            fields.push_str(&format!("    {}\n", <Field as Into<String>>::into(field)));
+        for field in value.fields {
+            for line in <Field as Into<String>>::into(field).lines() {
+                fields.push_str(&format!("    {}\n", line));
+            }
         }
 
         let mut methods = String::new();
 
-        for method in self.methods {
-            methods.push_str(&format!("    {}\n", <Method as Into<String>>::into(method)));
+        for method in value.methods {
+            for line in <Method as Into<String>>::into(method).lines() {
+                if line.is_empty() {
+                    methods.push('\n');
+                } else {
+                    methods.push_str(&format!("    {}\n", line));
+                }
+            }
         }
 
-        let implements = if self.interfaces.is_empty() {
+        let extends = value
+            .superclass
+            .map(|superclass| format!(" extends {}", String::from(superclass)))
+            .unwrap_or_default();
+
+        let implements = if value.interfaces.is_empty() {
             "".to_string()
         } else {
-            let interfaces = self.interfaces
+            let interfaces = value.interfaces
                 .iter()
-                .map(|interface| interface.name().clone())
+                .map(|interface| interface.declaration())
                 .collect::<Vec<String>>()
                 .join(", ");
             
             format!(" implements {}", interfaces)
         };
         
-        let package_string = if self.package.is_empty() {
+        let package_string = if value.package.is_empty() {
             "".to_string()
         } else {
-            format!("package {};", self.package)
+            format!("package {};", value.package)
         };
         
+        let annotations = value
+            .annotations
+            .into_iter()
+            .map(|a| format!("{}\n", String::from(a)))
+            .collect::<String>();
+
+        let doc = value.doc.map(|d| javadoc(&d)).unwrap_or_default();
+
         format!(
-            "{package_string}\n\n{imports}\n\npublic class {}{implements} {{\n{fields}\n{methods}\n}}",
-            self.name
+            "{package_string}\n\n{imports}\n\n{doc}{annotations}public class {}{extends}{implements} {{\n{fields}\n{methods}\n}}",
+            value.name
         )
     }
 }
\ No newline at end of file
@@ -1,22 +1,62 @@
+use crate::Type;
+
 pub struct Interface {
     name: String,
     package: String,
+    generics: Vec<Type>,
 }
 
 impl Interface {
-    pub fn new(name: String, package: String) -> Self { Self { name, package }}
+    pub fn new(name: String, package: String) -> Self {
+        Self {
+            name,
+            package,
+            generics: vec![],
+        }
+    }
     
     pub fn package_required(&self) -> String {
         format!("{}.{}", self.package, self.name)
     }
+
+    /// Imports of the interface and of its generics
+    pub fn packages_required(&self) -> Vec<String> {
+        let mut packages = vec![self.package_required()];
+
+        for generic in self.generics.iter() {
+            packages.extend(generic.packages_required());
+        }
+
+        packages
+    }
     
     pub fn name(&self) -> &String {
         &self.name
     }
+
+    pub fn add_generic(&mut self, generic: Type) {
+        self.generics.push(generic);
+    }
+
+    /// Name of the interface with its generics, as written after `implements`
+    pub fn declaration(&self) -> String {
+        if self.generics.is_empty() {
+            return self.name.clone();
+        }
+
+        let generics = self
+            .generics
+            .iter()
+            .map(|g| String::from(g.clone()))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        format!("{}<{generics}>", self.name)
+    }
 }
 
-impl Into<String> for Interface {
-    fn into(self) -> String {
-        format!(r#"interface {}"#, self.name)
+impl From<Interface> for String {
+    fn from(value: Interface) -> Self {
+        format!(r#"interface {}"#, value.name)
     }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+pub(crate) mod annotation;
 pub(crate) mod basic;
 pub(crate) mod class;
 pub(crate) mod record;
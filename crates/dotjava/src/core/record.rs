@@ -29,15 +29,15 @@ impl Record {
     }
 }
 
-impl Into<String> for Record {
-    fn into(self) -> String {
+impl From<Record> for String {
+    fn from(value: Record) -> Self {
         let mut imports = String::new();
 
-        for import in self.imports {
+        for import in value.imports {
             imports.push_str(&format!("import {};\n", import));
         }
 
-        let fields = self
+        let fields = value
             .fields
             .into_iter()
             .map(|f| f.into_record_field_string())
@@ -46,7 +46,7 @@ impl Into<String> for Record {
 
         format!(
             "package {};\n\n{imports}\n\npublic record {}({fields}) {{}}",
-            self.package, self.name
+            value.package, value.name
         )
     }
 }
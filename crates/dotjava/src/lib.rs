@@ -5,6 +5,7 @@ mod reader;
 #[allow(unused)]
 mod writer;
 
+pub use core::annotation::*;
 pub use core::basic::*;
 pub use core::class::*;
 pub use core::record::*;